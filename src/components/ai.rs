@@ -0,0 +1,135 @@
+/// A small state machine for enemy behavior, authored in the enemy prefabs.
+/// Each state picks a movement strategy (and optionally a speed) and whether
+/// the enemy's launcher fires. Transitions are checked in order every frame
+/// by `AiSystem`, which runs before `MovementTrackingSystem`, and the first
+/// transition whose condition holds moves the enemy to the named state.
+use amethyst::{
+    assets::PrefabData,
+    derive::PrefabData,
+    ecs::{storage::DenseVecStorage, Component, Entity, WriteStorage},
+    Error,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::movement::MovementType;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum Condition {
+    // the targeted player is closer than this distance
+    PlayerWithin(f32),
+    // the targeted player is further away than this distance
+    PlayerBeyond(f32),
+    // the enemy's health is at or below this amount
+    HealthBelow(f32),
+    // at least this many seconds have passed since entering the current state
+    TimeInState(f32),
+    // nothing blocks a straight line to the targeted player
+    LineOfSight,
+    // something blocks a straight line to the targeted player
+    NoLineOfSight,
+}
+
+// everything a condition might need to know, gathered by `AiSystem`
+#[derive(Clone, Copy, Debug)]
+pub struct Senses {
+    pub player_distance: Option<f32>,
+    pub health: Option<f32>,
+    pub line_of_sight: bool,
+}
+
+impl Condition {
+    fn holds(self, senses: &Senses, time_in_state: f32) -> bool {
+        match self {
+            Condition::PlayerWithin(distance) => senses.player_distance.map_or(false, |d| d < distance),
+            Condition::PlayerBeyond(distance) => senses.player_distance.map_or(true, |d| d > distance),
+            Condition::HealthBelow(health) => senses.health.map_or(false, |h| h <= health),
+            Condition::TimeInState(seconds) => time_in_state >= seconds,
+            Condition::LineOfSight => senses.player_distance.is_some() && senses.line_of_sight,
+            Condition::NoLineOfSight => senses.player_distance.is_some() && !senses.line_of_sight,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AiTransition {
+    pub condition: Condition,
+    // name of the state to switch to
+    pub to: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AiState {
+    pub name: String,
+    pub movement_type: MovementType,
+    // None keeps the speed from the enemy's `Movement`
+    #[serde(default)]
+    pub speed: Option<f32>,
+    // whether the enemy's launcher (if it has one) fires in this state
+    #[serde(default = "firing")]
+    pub firing: bool,
+    #[serde(default)]
+    pub transitions: Vec<AiTransition>,
+}
+
+fn firing() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+#[serde(deny_unknown_fields)]
+pub struct Ai {
+    // the first state is the starting state
+    pub states: Vec<AiState>,
+
+    #[serde(skip)]
+    current: Option<usize>,
+
+    #[serde(skip)]
+    time_in_state: f32,
+}
+
+impl Ai {
+    // advances the state machine and returns the state to apply if the enemy
+    // just entered it (including the starting state on the first update)
+    pub fn update(&mut self, time: f32, senses: &Senses) -> Option<&AiState> {
+        let current = match self.current {
+            Some(current) => current,
+            None => {
+                self.enter(0);
+                return self.states.first();
+            },
+        };
+
+        self.time_in_state += time;
+
+        let time_in_state = self.time_in_state;
+        let next = self.states.get(current).and_then(|state| {
+            state
+                .transitions
+                .iter()
+                .find(|transition| transition.condition.holds(senses, time_in_state))
+                .and_then(|transition| self.states.iter().position(|s| s.name == transition.to))
+        });
+
+        match next {
+            Some(next) => {
+                self.enter(next);
+                self.states.get(next)
+            },
+            None => None,
+        }
+    }
+
+    fn enter(&mut self, index: usize) {
+        self.current = Some(index);
+        self.time_in_state = 0.0;
+    }
+}
+
+impl Component for Ai {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,161 @@
+/// Frame based sprite animations, authored per archetype in the prefabs. Each
+/// clip is a list of sprite numbers from the entity's sprite sheet, shown for
+/// `frame_duration` seconds each. `AnimationSystem` advances the current clip,
+/// writes the frame to the entity's `SpriteRender`, and sends an
+/// `AnimationFinished` event when a clip that plays once reaches its end.
+use std::collections::HashMap;
+
+use amethyst::{
+    assets::PrefabData,
+    derive::PrefabData,
+    ecs::{storage::DenseVecStorage, Component, Entity, WriteStorage},
+    Error,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum AnimationId {
+    // standing still
+    Idle,
+    // moving at any speed
+    Move,
+    // just took damage
+    Hit,
+    // dying. entities are removed once a Death clip finishes
+    Death,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Playback {
+    Loop,
+    Once,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Playback::Loop
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnimationClip {
+    // sprite numbers, in the order they're shown
+    pub frames: Vec<usize>,
+    // seconds each frame is shown for
+    pub frame_duration: f32,
+    #[serde(default)]
+    pub playback: Playback,
+}
+
+// sent by `AnimationSystem` when a clip that plays once shows its last frame
+// for its full duration. looping clips never finish
+#[derive(Clone, Copy, Debug)]
+pub struct AnimationFinished {
+    pub entity: Entity,
+    pub id: AnimationId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+#[serde(deny_unknown_fields)]
+pub struct Animation {
+    pub clips: HashMap<AnimationId, AnimationClip>,
+
+    #[serde(skip)]
+    current: Option<AnimationId>,
+
+    #[serde(skip)]
+    frame: usize,
+
+    #[serde(skip)]
+    elapsed: f32,
+
+    #[serde(skip)]
+    finished: bool,
+}
+
+impl Animation {
+    pub fn current(&self) -> Option<AnimationId> {
+        self.current
+    }
+
+    pub fn has_clip(&self, id: AnimationId) -> bool {
+        self.clips.contains_key(&id)
+    }
+
+    // swaps in new clips (e.g. for a boss phase), keeping any the new set
+    // doesn't replace. a replaced clip that's playing starts over
+    pub fn replace_clips(&mut self, clips: &HashMap<AnimationId, AnimationClip>) {
+        for (id, clip) in clips {
+            self.clips.insert(*id, clip.clone());
+
+            if self.current == Some(*id) {
+                self.restart(*id);
+            }
+        }
+    }
+
+    // switches to a clip unless it's already playing. clips the prefab doesn't
+    // define are ignored, so the current clip keeps going
+    pub fn play(&mut self, id: AnimationId) {
+        if self.current != Some(id) {
+            self.restart(id);
+        }
+    }
+
+    // starts a clip from its first frame, even if it's already playing
+    pub fn restart(&mut self, id: AnimationId) {
+        if self.clips.contains_key(&id) {
+            self.current = Some(id);
+            self.frame = 0;
+            self.elapsed = 0.0;
+            self.finished = false;
+        }
+    }
+
+    // whether a clip that plays once (such as Hit or Death) is still showing,
+    // which other clips shouldn't interrupt. a finished Death clip also counts,
+    // since there's nothing to go back to
+    pub fn is_busy(&self) -> bool {
+        match self.current {
+            Some(AnimationId::Death) => true,
+            Some(id) => self.clips[&id].playback == Playback::Once && !self.finished,
+            None => false,
+        }
+    }
+
+    // moves the current clip forward, returning the sprite number to show and
+    // whether the clip finished during this step
+    pub fn advance(&mut self, delta_seconds: f32) -> Option<(usize, bool)> {
+        let clip = self.clips.get(&self.current?)?;
+        if clip.frames.is_empty() {
+            return None;
+        }
+
+        let mut just_finished = false;
+        if !self.finished && clip.frame_duration > 0.0 {
+            self.elapsed += delta_seconds;
+
+            while self.elapsed >= clip.frame_duration && !self.finished {
+                self.elapsed -= clip.frame_duration;
+
+                if self.frame + 1 < clip.frames.len() {
+                    self.frame += 1;
+                } else if clip.playback == Playback::Loop {
+                    self.frame = 0;
+                } else {
+                    self.finished = true;
+                    just_finished = true;
+                }
+            }
+        }
+
+        Some((clip.frames[self.frame], just_finished))
+    }
+}
+
+impl Component for Animation {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,99 @@
+/// Bosses are regular enemies (see `entities::enemy`) with an extra `Boss`
+/// component. The boss moves through a list of phases as its health drops,
+/// and each phase swaps in its own movement, fire pattern, speed and sprite.
+/// `BossSystem` is responsible for switching phases.
+use std::collections::HashMap;
+
+use amethyst::{
+    assets::PrefabData,
+    derive::PrefabData,
+    ecs::{storage::DenseVecStorage, Component, Entity, WriteStorage},
+    Error,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::{
+    animation::{AnimationClip, AnimationId},
+    launcher::FirePattern,
+    movement::MovementType,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BossPhase {
+    // the phase starts once health drops to this fraction of the boss's starting
+    // health, so the first phase should use 1.0
+    pub health_threshold: f32,
+    pub movement_type: MovementType,
+    pub speed: f32,
+    pub pattern: FirePattern,
+    // sprite from the enemy sprite sheet. None keeps the current sprite. an
+    // animated boss sets its sprite every frame, so it uses `clips` instead
+    #[serde(default)]
+    pub sprite_number: Option<usize>,
+    // animation clips that replace the boss's clips of the same name
+    #[serde(default)]
+    pub clips: HashMap<AnimationId, AnimationClip>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+#[serde(deny_unknown_fields)]
+pub struct Boss {
+    // ordered from the highest health threshold to the lowest
+    pub phases: Vec<BossPhase>,
+
+    // the enemy's health when the boss spawned, set by `EnemyPrefab`
+    #[serde(skip)]
+    max_health: f32,
+
+    #[serde(skip)]
+    current_phase: Option<usize>,
+}
+
+impl Boss {
+    // a copy of this boss that measures its phases against `max_health`
+    pub fn with_max_health(&self, max_health: f32) -> Boss {
+        Boss {
+            max_health,
+            ..self.clone()
+        }
+    }
+
+    // fraction of health remaining, from 0.0 to 1.0
+    pub fn health_fraction(&self, health: f32) -> f32 {
+        if self.max_health <= 0.0 {
+            0.0
+        } else {
+            (health / self.max_health).max(0.0).min(1.0)
+        }
+    }
+
+    // whether the boss has picked its first phase yet
+    pub fn has_started(&self) -> bool {
+        self.current_phase.is_some()
+    }
+
+    // returns the phase to switch to if the boss has crossed a new threshold
+    // since the last check (or hasn't started a phase yet)
+    pub fn next_phase(&mut self, health: f32) -> Option<BossPhase> {
+        let fraction = self.health_fraction(health);
+
+        let phase_index = self
+            .phases
+            .iter()
+            .rposition(|phase| fraction <= phase.health_threshold)?;
+
+        if self.current_phase == Some(phase_index) {
+            None
+        } else {
+            self.current_phase = Some(phase_index);
+            Some(self.phases[phase_index].clone())
+        }
+    }
+}
+
+impl Component for Boss {
+    type Storage = DenseVecStorage<Self>;
+}
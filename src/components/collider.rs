@@ -46,6 +46,60 @@ impl Collider {
         let this_aabb = self.aabb_from_coordinates(x, y);
         this_aabb.intersects(other)
     }
+
+    // the smallest offset that moves this collider (at x, y) out of `other`, or
+    // None if they don't overlap. pushing out along the shallower axis only is
+    // what lets movers slide along walls instead of sticking to them
+    pub fn push_out(self, x: f32, y: f32, other: &AABB<f32>) -> Option<(f32, f32)> {
+        let this_aabb = self.aabb_from_coordinates(x, y);
+        if !this_aabb.intersects(other) {
+            return None;
+        }
+
+        let left = this_aabb.maxs().x - other.mins().x;
+        let right = other.maxs().x - this_aabb.mins().x;
+        let down = this_aabb.maxs().y - other.mins().y;
+        let up = other.maxs().y - this_aabb.mins().y;
+
+        let push_x = if left < right { -left } else { right };
+        let push_y = if down < up { -down } else { up };
+
+        if push_x.abs() < push_y.abs() {
+            Some((push_x, 0.0))
+        } else {
+            Some((0.0, push_y))
+        }
+    }
+}
+
+// whether the line from (x1, y1) to (x2, y2) passes through the box. this uses
+// the slab method: the line is clipped against the box one axis at a time, and
+// misses if nothing is left
+pub fn segment_intersects(aabb: &AABB<f32>, x1: f32, y1: f32, x2: f32, y2: f32) -> bool {
+    let (mut t_min, mut t_max) = (0.0_f32, 1.0_f32);
+    let axes = [
+        (x1, x2 - x1, aabb.mins().x, aabb.maxs().x),
+        (y1, y2 - y1, aabb.mins().y, aabb.maxs().y),
+    ];
+
+    for &(start, delta, min, max) in axes.iter() {
+        if delta.abs() < std::f32::EPSILON {
+            if start < min || start > max {
+                return false;
+            }
+        } else {
+            let t1 = (min - start) / delta;
+            let t2 = (max - start) / delta;
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+
+            if t_min > t_max {
+                return false;
+            }
+        }
+    }
+
+    true
 }
 
 impl Component for Collider {
@@ -0,0 +1,90 @@
+/// Shared firing cooldown for anything with a weapon (the player's lasers and
+/// enemy launchers). `CooldownSystem` ticks every cooldown each frame, so the
+/// timer keeps running even when nothing is firing. A cooldown can also fire in
+/// bursts: `burst_size` shots separated by `fire_delay`, then a longer
+/// `reload_delay` before the next burst.
+use amethyst::{
+    assets::PrefabData,
+    derive::PrefabData,
+    ecs::{storage::DenseVecStorage, Component, Entity, WriteStorage},
+    Error,
+};
+
+use serde::{Deserialize, Serialize};
+
+// seconds between shots for launchers that don't set their own cooldown
+const DEFAULT_FIRE_DELAY: f32 = 1.5;
+
+fn default_burst_size() -> u32 {
+    1
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+#[serde(deny_unknown_fields)]
+pub struct Cooldown {
+    // seconds between shots (or between shots within a burst)
+    pub fire_delay: f32,
+
+    // number of shots before reloading. 1 means a steady rate of fire
+    #[serde(default = "default_burst_size")]
+    pub burst_size: u32,
+
+    // seconds to wait after the last shot in a burst. ignored if `burst_size` is 1
+    #[serde(default)]
+    pub reload_delay: f32,
+
+    // seconds until the next shot is allowed. this starts at zero so the first
+    // shot is instant, but prefabs can set it to delay the first shot
+    #[serde(default)]
+    pub remaining: f32,
+
+    #[serde(skip)]
+    shots_fired: u32,
+}
+
+impl Default for Cooldown {
+    fn default() -> Self {
+        Cooldown {
+            fire_delay: DEFAULT_FIRE_DELAY,
+            burst_size: default_burst_size(),
+            reload_delay: 0.0,
+            remaining: 0.0,
+            shots_fired: 0,
+        }
+    }
+}
+
+impl Cooldown {
+    pub fn tick(&mut self, time: f32) {
+        self.remaining = (self.remaining - time).max(0.0);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    // checks if the cooldown has elapsed and, if so, restarts it for the next
+    // shot. like the old `can_fire` methods this means callers don't have to
+    // manage the timer themselves
+    pub fn try_fire(&mut self) -> bool {
+        if !self.is_ready() {
+            return false;
+        }
+
+        self.shots_fired += 1;
+
+        if self.burst_size > 1 && self.shots_fired >= self.burst_size {
+            self.shots_fired = 0;
+            self.remaining = self.reload_delay;
+        } else {
+            self.remaining = self.fire_delay;
+        }
+
+        true
+    }
+}
+
+impl Component for Cooldown {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,63 @@
+/// Damage flashes. `HitFlash` pulses an entity's tint when it takes damage,
+/// using the same `Fader` ramp as the level transitions, and `EffectsSystem`
+/// removes it (restoring the old tint) once the pulse has played out. Death
+/// effects are particle bursts, requested through `ParticleBursts`.
+use amethyst::{
+    ecs::prelude::{Component, DenseVecStorage, Entity, WriteStorage},
+    renderer::{palette::Srgba, resources::Tint},
+};
+
+use crate::resources::fade::{Fade, Fader};
+
+// how fast the flash ramps up and back down. the whole pulse takes 2 / speed seconds
+const FLASH_SPEED: f32 = 12.0;
+const FLASH_COLOR: (f32, f32, f32) = (1.0, 0.15, 0.15);
+
+pub struct HitFlash {
+    fader: Fader,
+    // the tint to restore once the flash is over
+    previous: Option<Tint>,
+}
+
+impl Component for HitFlash {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl HitFlash {
+    // the tint for this frame, or None once the pulse has finished
+    pub fn next_tint(&mut self, time_delta: f32) -> Option<Tint> {
+        let amount = self.fader.next_alpha_change(time_delta).max(0.0).min(1.0);
+        if self.fader.fade_completed() {
+            return None;
+        }
+
+        let base = self.previous.map_or(Srgba::new(1.0, 1.0, 1.0, 1.0), |tint| tint.0);
+        let (red, green, blue) = FLASH_COLOR;
+
+        Some(Tint(Srgba::new(
+            base.red + (red - base.red) * amount,
+            base.green + (green - base.green) * amount,
+            base.blue + (blue - base.blue) * amount,
+            base.alpha,
+        )))
+    }
+
+    pub fn previous(&self) -> Option<Tint> {
+        self.previous
+    }
+}
+
+// starts (or restarts) a damage flash. an entity hit again mid-flash keeps the
+// tint it had before the first hit
+pub fn flash(entity: Entity, flashes: &mut WriteStorage<HitFlash>, tints: &WriteStorage<Tint>) {
+    let previous = match flashes.get(entity) {
+        Some(current) => current.previous,
+        None => tints.get(entity).copied(),
+    };
+
+    let hit_flash = HitFlash {
+        fader: Fader::new(FLASH_SPEED, Fade::Darken),
+        previous,
+    };
+    flashes.insert(entity, hit_flash).expect("unable to add hit flash");
+}
@@ -0,0 +1,62 @@
+/// Hazard tiles from the level grid. Each hazard is a trigger volume (a
+/// `Collider` that doesn't block anything) plus one of the effect components
+/// below, and `HazardSystem` applies the effect to any player overlapping it.
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+use crate::{
+    components::collider::Collider,
+    resources::level::{CELL_HEIGHT, CELL_WIDTH},
+};
+
+// hazards fill one cell of the level grid
+pub const HAZARD_COLLIDER: Collider = Collider {
+    half_width: CELL_WIDTH * 0.5,
+    half_height: CELL_HEIGHT * 0.5,
+};
+
+// electrified floor, which drains a player's health while they stand on it
+#[derive(Clone, Copy, Debug)]
+pub struct DamageZone {
+    pub damage_per_second: f32,
+}
+
+impl Default for DamageZone {
+    fn default() -> Self {
+        DamageZone {
+            damage_per_second: 40.0,
+        }
+    }
+}
+
+impl Component for DamageZone {
+    type Storage = DenseVecStorage<Self>;
+}
+
+// scales the speed of players standing in it. overlapping zones don't stack,
+// the slowest one wins
+#[derive(Clone, Copy, Debug)]
+pub struct SlowZone {
+    pub speed_scale: f32,
+}
+
+impl Default for SlowZone {
+    fn default() -> Self {
+        SlowZone { speed_scale: 0.5 }
+    }
+}
+
+impl Component for SlowZone {
+    type Storage = DenseVecStorage<Self>;
+}
+
+// one of a pair of teleporter pads. players stepping on it are moved to the
+// other pad, and have to step off before it works again
+#[derive(Clone, Copy, Debug)]
+pub struct Teleporter {
+    pub destination_x: f32,
+    pub destination_y: f32,
+}
+
+impl Component for Teleporter {
+    type Storage = DenseVecStorage<Self>;
+}
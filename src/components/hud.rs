@@ -0,0 +1,23 @@
+/// Markers for HUD elements that systems need to update every frame.
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+// the fill portion of the boss health bar. its `UiTransform` width is scaled
+// from `full_width` down to zero as the boss takes damage
+#[derive(Debug)]
+pub struct BossHealthBar {
+    pub full_width: f32,
+}
+
+impl Component for BossHealthBar {
+    type Storage = DenseVecStorage<Self>;
+}
+
+// the lives and score readout for one player, updated by `PlayerHudSystem`
+#[derive(Debug)]
+pub struct PlayerHud {
+    pub index: usize,
+}
+
+impl Component for PlayerHud {
+    type Storage = DenseVecStorage<Self>;
+}
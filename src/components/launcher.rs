@@ -1,100 +1,214 @@
-/// This component tracks when and how to fire projectiles,
-/// along with logic to create different projectiles.
+/// This component tracks how to fire projectiles, along with logic to
+/// create different projectiles. Each launcher has a `FirePattern` that
+/// decides how many projectiles a volley has and which way they go. How often
+/// it fires comes from the entity's `Cooldown` component.
 use amethyst::{
     assets::PrefabData,
-    core::Transform,
+    core::{math::Vector3, Hidden, Transform},
     derive::PrefabData,
-    ecs::prelude::{Component, DenseVecStorage, Entities, Entity, LazyUpdate, ReadExpect, WriteStorage},
+    ecs::prelude::{Component, DenseVecStorage, Entity, WriteStorage},
     renderer::{sprite::SpriteSheetHandle, SpriteRender},
     Error,
 };
 
 use serde::{Deserialize, Serialize};
 
-use crate::components::{
-    cleanup::CleanupTag,
-    collider::Collider,
-    movement::{Movement, MovementType},
+use std::f32::consts::PI;
+
+use crate::{
+    components::{
+        animation::Animation,
+        collider::Collider,
+        movement::{Movement, MovementType},
+    },
+    resources::pool::{Pool, ShotStorages},
 };
 
+// angles in the prefab files are in degrees since they're easier to tweak by hand
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum FirePattern {
+    // a single projectile aimed at the player. combined with a `Cooldown`
+    // that has a `burst_size`, this is an aimed burst
+    Aimed,
+    // `count` projectiles evenly spaced in a full circle
+    Radial { count: u32 },
+    // a radial ring that rotates by `step_degrees` after every volley
+    Spiral { count: u32, step_degrees: f32 },
+    // `count` projectiles spread across `spread_degrees`, centered on the player
+    Fan { count: u32, spread_degrees: f32 },
+}
+
+impl Default for FirePattern {
+    fn default() -> Self {
+        FirePattern::Aimed
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PrefabData)]
 #[prefab(Component)]
 #[serde(deny_unknown_fields)]
+// the rate of fire comes from a separate `Cooldown` component
 pub struct Launcher {
-    pub fire_delay: f32,
     pub projectile_speed: f32,
-    pub seconds_since_firing: f32,
+
+    #[serde(default)]
+    pub pattern: FirePattern,
+
+    // when true, this launcher's projectiles are removed once the entity that
+    // fired them is gone (used for bosses)
+    #[serde(default)]
+    pub clear_on_death: bool,
+
+    // AI states can turn the launcher off (see `components::ai`)
+    #[serde(skip, default = "enabled")]
+    pub enabled: bool,
+
+    // current rotation (in radians) for spiral patterns
+    #[serde(skip)]
+    spiral_angle: f32,
+}
+
+fn enabled() -> bool {
+    true
 }
 
 impl Launcher {
-    // checks if we've had enough time elapse since the last laser
-    // and resets the timer. this is possibly a surprising API for a
-    // `bool` check, but it also ensures we don't rely on calling code
-    // to manage the timer.
-    pub fn can_fire(&mut self, time: f32) -> bool {
-        if self.seconds_since_firing >= self.fire_delay {
-            self.seconds_since_firing = 0.0;
-            true
-        } else {
-            self.seconds_since_firing += time;
-            false
+    // computes the angle (in radians) of each projectile in the next volley.
+    // `aim_angle` is the angle towards the player, if there is one. patterns
+    // that aim at the player don't fire without a target
+    pub fn next_volley(&mut self, aim_angle: Option<f32>) -> Vec<f32> {
+        match self.pattern {
+            FirePattern::Aimed => aim_angle.into_iter().collect(),
+            FirePattern::Radial { count } => ring(0.0, count),
+            FirePattern::Spiral { count, step_degrees } => {
+                let angles = ring(self.spiral_angle, count);
+                self.spiral_angle = (self.spiral_angle + step_degrees.to_radians()) % (2.0 * PI);
+                angles
+            },
+            FirePattern::Fan { count, spread_degrees } => aim_angle
+                .map(|aim| fan(aim, count, spread_degrees.to_radians()))
+                .unwrap_or_default(),
         }
     }
 }
 
+fn ring(offset: f32, count: u32) -> Vec<f32> {
+    let step = 2.0 * PI / count.max(1) as f32;
+    (0 .. count).map(|i| offset + i as f32 * step).collect()
+}
+
+fn fan(center: f32, count: u32, spread: f32) -> Vec<f32> {
+    if count <= 1 {
+        return vec![center];
+    }
+
+    let step = spread / (count - 1) as f32;
+    let start = center - spread * 0.5;
+    (0 .. count).map(|i| start + i as f32 * step).collect()
+}
+
 impl Component for Launcher {
     type Storage = DenseVecStorage<Self>;
 }
 
-// empty struct for now because this is used as a way to track projectiles
-// in systems, and so far there's no real data we need to associate with it
+// used as a way to track projectiles in systems. the owner is only set for
+// launchers with `clear_on_death`, so their projectiles can be removed when
+// the owner is
 #[derive(Debug)]
-pub struct Projectile;
+pub struct Projectile {
+    pub owner: Option<Entity>,
+}
 
 impl Component for Projectile {
     type Storage = DenseVecStorage<Self>;
 }
 
-// this needs to be run by a system that has a launcher, sprites, transforms,
-// and all entities.
-pub fn launch_projectile(
-    launcher: Launcher,
-    sprite_sheet_handle: SpriteSheetHandle,
+// the components for one projectile from `launcher`, flying in a straight line
+// at `angle`. `launch_projectile` puts them on a pooled entity
+pub fn build_projectile(
+    launcher: &Launcher,
+    launcher_entity: Entity,
+    angle: f32,
     base_transform: &Transform,
-    entities: &Entities,
-    lazy_update: &ReadExpect<LazyUpdate>,
-) {
-    // an incorrect sprite number here will lead to a memory leak
-    let sprite_render = SpriteRender {
-        sprite_sheet: sprite_sheet_handle,
-        sprite_number: 3,
-    };
-
+) -> (Projectile, Movement, Transform) {
     let transform = base_transform.clone();
 
+    // the direction is frozen up front so `ProjectileRush` doesn't re-aim at
+    // the player. the locked direction is only used to rotate the sprite, and
+    // needs to be far enough away that the projectile won't pass it before
+    // the rotation is set
+    let (sin, cos) = angle.sin_cos();
+    let origin = transform.translation();
     let movement = Movement {
         speed: launcher.projectile_speed,
-        velocity_x: 0.0,
-        velocity_y: 0.0,
-        freeze_direction: false,
-        locked_direction: None,
+        velocity_x: launcher.projectile_speed * cos,
+        velocity_y: launcher.projectile_speed * sin,
+        freeze_direction: true,
+        locked_direction: Some(Vector3::new(origin.x + cos * 1000.0, origin.y + sin * 1000.0, origin.z)),
         already_rotated: false,
         movement_type: MovementType::ProjectileRush,
+        ..Movement::default()
+    };
+
+    let projectile = Projectile {
+        owner: if launcher.clear_on_death {
+            Some(launcher_entity)
+        } else {
+            None
+        },
+    };
+
+    (projectile, movement, transform)
+}
+
+// puts a projectile from `build_projectile` into play, reusing a recycled
+// projectile (and the sprite and collider it already has) when there is one.
+// if the pool is at its cap the projectile is skipped
+#[allow(clippy::too_many_arguments)]
+pub fn launch_projectile(
+    (projectile, movement, transform): (Projectile, Movement, Transform),
+    sprite_sheet_handle: &SpriteSheetHandle,
+    animation: Option<&Animation>,
+    projectiles: &mut WriteStorage<Projectile>,
+    movements: &mut WriteStorage<Movement>,
+    colliders: &mut WriteStorage<Collider>,
+    pool: &mut Pool<Projectile>,
+    shots: &mut ShotStorages,
+) {
+    // an incorrect sprite number here will lead to a memory leak
+    let sprite_render = SpriteRender {
+        sprite_sheet: sprite_sheet_handle.clone(),
+        sprite_number: 3,
     };
 
-    let collider = Collider {
-        half_width: 16.0,
-        half_height: 16.0,
+    let (projectile_entity, reused) = match shots.acquire(pool, transform, &sprite_render, animation) {
+        Some(acquired) => acquired,
+        None => return,
     };
 
-    let projectile = Projectile {};
-    let cleanup_tag = CleanupTag {};
+    if !reused {
+        let collider = Collider {
+            half_width: 16.0,
+            half_height: 16.0,
+        };
+        colliders.insert(projectile_entity, collider).expect("unable to launch projectile");
+    }
+
+    projectiles.insert(projectile_entity, projectile).expect("unable to launch projectile");
+    movements.insert(projectile_entity, movement).expect("unable to launch projectile");
+}
 
-    let projectile_entity: Entity = entities.create();
-    lazy_update.insert(projectile_entity, projectile);
-    lazy_update.insert(projectile_entity, cleanup_tag);
-    lazy_update.insert(projectile_entity, movement);
-    lazy_update.insert(projectile_entity, transform);
-    lazy_update.insert(projectile_entity, collider);
-    lazy_update.insert(projectile_entity, sprite_render);
+// takes a projectile out of play. its movement goes too, so the movement
+// systems leave it alone while it waits in the pool
+pub fn recycle_projectile(
+    projectile_entity: Entity,
+    projectiles: &mut WriteStorage<Projectile>,
+    movements: &mut WriteStorage<Movement>,
+    hiddens: &mut WriteStorage<Hidden>,
+    pool: &mut Pool<Projectile>,
+) {
+    if projectiles.contains(projectile_entity) {
+        movements.remove(projectile_entity);
+        pool.recycle(projectile_entity, projectiles, hiddens);
+    }
 }
@@ -1,4 +1,15 @@
+pub mod ai;
+pub mod animation;
+pub mod boss;
 pub mod cleanup;
 pub mod collider;
+pub mod cooldown;
+pub mod effects;
+pub mod hazard;
+pub mod hud;
 pub mod launcher;
 pub mod movement;
+pub mod particles;
+pub mod spawnpoint;
+pub mod steering;
+pub mod targeting;
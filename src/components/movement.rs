@@ -6,23 +6,69 @@ use amethyst::{
     Error,
 };
 
+use rand::Rng;
+
 use serde::{Deserialize, Serialize};
 
+use std::f32::consts::PI;
+
+use crate::resources::playablearea::PlayableArea;
+
+// how far ahead (in radians) orbiting enemies aim along their circle
+const ORBIT_LEAD: f32 = 0.5;
+
+// wandering enemies pick a new destination once they're this close to the old one
+const WANDER_ARRIVAL_DISTANCE: f32 = 20.0;
+
+// the parameters for each movement type are set in the enemy prefabs, e.g.
+// `movement_type: Orbit(radius: 250.0)`
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum MovementType {
     Gravitate,
     HorizontalRush,
     ProjectileRush,
+    // circle the player at `radius`
+    Orbit { radius: f32 },
+    // move towards the player while weaving side to side. `amplitude` is the
+    // sideways speed relative to the forward speed and `frequency` is the
+    // number of full weaves per second
+    Zigzag { amplitude: f32, frequency: f32 },
+    // pick random spots in the playable area, choosing a new spot after
+    // `interval` seconds or when the current one is reached
+    Wander { interval: f32 },
+    // run away when the player is closer than `distance`, otherwise wander
+    Flee { distance: f32, interval: f32 },
+    // circle sideways while trying to stay `distance` away from the player
+    Strafe { distance: f32 },
     //PlayerControl,
 }
 
+// what a mover does at the edges of the playable area. enemies usually spawn
+// outside the arena, so `Clamp` and `Bounce` only kick in once the mover has
+// made it inside
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum Boundary {
+    // move freely, and get culled after going too far outside the arena
+    Cull,
+    // stop at the arena walls
+    Clamp,
+    // reverse direction at the arena walls
+    Bounce,
+}
+
+impl Default for Boundary {
+    fn default() -> Self {
+        Boundary::Cull
+    }
+}
+
 impl Default for MovementType {
     fn default() -> Self {
         MovementType::Gravitate
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize, PrefabData)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PrefabData)]
 #[prefab(Component)]
 #[serde(deny_unknown_fields)]
 pub struct Movement {
@@ -33,6 +79,30 @@ pub struct Movement {
     pub locked_direction: Option<Vector3<f32>>,
     pub already_rotated: bool,
     pub movement_type: MovementType,
+
+    #[serde(default)]
+    pub boundary: Boundary,
+
+    // set once the mover is inside the playable area
+    #[serde(skip)]
+    pub entered_arena: bool,
+
+    // seconds spent with this movement, used by time based strategies
+    #[serde(skip)]
+    pub elapsed: f32,
+
+    // current destination and seconds until picking a new one (wandering)
+    #[serde(skip)]
+    pub wander_target: Option<(f32, f32)>,
+    #[serde(skip)]
+    pub wander_timer: f32,
+
+    // extra velocity from `SteeringSystem`, added on top of the velocity
+    // chosen by the movement type
+    #[serde(skip)]
+    pub steer_x: f32,
+    #[serde(skip)]
+    pub steer_y: f32,
 }
 
 impl Component for Movement {
@@ -54,11 +124,103 @@ impl Movement {
         self.speed
     }
 
-    pub fn next_move(&mut self, target_x: f32, target_y: f32, target_z: f32, current_x: f32, current_y: f32) {
+    // applies the `boundary` and returns the corrected position
+    pub fn confine(&mut self, x: f32, y: f32, playable_area: &PlayableArea) -> (f32, f32) {
+        if playable_area.contains(x, y, 0.0) {
+            self.entered_arena = true;
+        }
+
+        if !self.entered_arena {
+            return (x, y);
+        }
+
+        let (clamped_x, clamped_y) = (playable_area.clamp_x(x), playable_area.clamp_y(y));
+
+        match self.boundary {
+            Boundary::Cull => (x, y),
+            Boundary::Clamp => (clamped_x, clamped_y),
+            // only movers with a frozen direction keep the flipped velocity,
+            // everyone else picks a new one on the next move anyway
+            Boundary::Bounce => {
+                if clamped_x != x {
+                    self.velocity_x = -self.velocity_x;
+                }
+                if clamped_y != y {
+                    self.velocity_y = -self.velocity_y;
+                }
+                (clamped_x, clamped_y)
+            },
+        }
+    }
+
+    // puts the mover back in the state it spawned in, for enemies that are
+    // culled back to their spawn point
+    pub fn reset(&mut self) {
+        self.velocity_x = 0.0;
+        self.velocity_y = 0.0;
+        self.freeze_direction = false;
+        self.locked_direction = None;
+        self.already_rotated = false;
+        self.wander_target = None;
+        self.entered_arena = false;
+    }
+
+    // the velocity including steering, capped at the movement's speed so a
+    // crowd doesn't push anyone faster than they'd normally go. movers
+    // without steering (like projectiles) keep their velocity untouched
+    pub fn steered_velocity(&self) -> (f32, f32) {
+        if self.steer_x == 0.0 && self.steer_y == 0.0 {
+            return (self.velocity_x, self.velocity_y);
+        }
+
+        let x = self.velocity_x + self.steer_x;
+        let y = self.velocity_y + self.steer_y;
+        let length = (x * x + y * y).sqrt();
+        if length > self.speed && length > 0.0 {
+            (x * self.speed / length, y * self.speed / length)
+        } else {
+            (x, y)
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn next_move(
+        &mut self,
+        target_x: f32,
+        target_y: f32,
+        target_z: f32,
+        current_x: f32,
+        current_y: f32,
+        time: f32,
+        playable_area: &PlayableArea,
+    ) {
+        self.elapsed += time;
+
         match self.movement_type {
             MovementType::Gravitate => self.move_towards(target_x, target_y, current_x, current_y),
             MovementType::HorizontalRush => self.rush_towards(target_x, target_y, target_z, current_x, current_y),
             MovementType::ProjectileRush => self.projectile_rush(target_x, target_y, target_z, current_x, current_y),
+            MovementType::Orbit { radius } => self.orbit(radius, target_x, target_y, current_x, current_y),
+            MovementType::Zigzag { amplitude, frequency } => {
+                self.zigzag(amplitude, frequency, target_x, target_y, current_x, current_y)
+            },
+            MovementType::Wander { interval } => self.wander(interval, time, current_x, current_y, playable_area),
+            MovementType::Flee { distance, interval } => {
+                self.flee(distance, interval, time, target_x, target_y, current_x, current_y, playable_area)
+            },
+            MovementType::Strafe { distance } => self.strafe(distance, target_x, target_y, current_x, current_y),
+        }
+    }
+
+    // sets the velocity along (dx, dy) at full speed. a zero vector stops moving
+    fn move_along(&mut self, dx: f32, dy: f32) {
+        let length = (dx * dx + dy * dy).sqrt();
+        if length > 0.0 {
+            self.velocity_x = self.get_speed() * dx / length;
+            self.velocity_y = self.get_speed() * dy / length;
+        } else {
+            self.velocity_x = 0.0;
+            self.velocity_y = 0.0;
         }
     }
 
@@ -74,6 +236,100 @@ impl Movement {
         self.velocity_y = self.get_speed() * angle.sin();
     }
 
+    // aims for a point a little further along the circle around the target,
+    // which pulls the enemy onto the circle and keeps it moving around it
+    pub fn orbit(&mut self, radius: f32, target_x: f32, target_y: f32, current_x: f32, current_y: f32) {
+        let angle = (current_y - target_y).atan2(current_x - target_x) + ORBIT_LEAD;
+        let goal_x = target_x + radius * angle.cos();
+        let goal_y = target_y + radius * angle.sin();
+        self.move_towards(goal_x, goal_y, current_x, current_y);
+    }
+
+    pub fn zigzag(
+        &mut self,
+        amplitude: f32,
+        frequency: f32,
+        target_x: f32,
+        target_y: f32,
+        current_x: f32,
+        current_y: f32,
+    ) {
+        let angle = (target_y - current_y).atan2(target_x - current_x);
+        let weave = amplitude * (self.elapsed * frequency * 2.0 * PI).sin();
+
+        // forward plus a sideways component perpendicular to it
+        let dx = angle.cos() - weave * angle.sin();
+        let dy = angle.sin() + weave * angle.cos();
+        self.move_along(dx, dy);
+    }
+
+    pub fn wander(&mut self, interval: f32, time: f32, current_x: f32, current_y: f32, playable_area: &PlayableArea) {
+        self.wander_timer -= time;
+
+        let arrived = self.wander_target.map_or(true, |(x, y)| {
+            (x - current_x).abs() <= WANDER_ARRIVAL_DISTANCE && (y - current_y).abs() <= WANDER_ARRIVAL_DISTANCE
+        });
+
+        if arrived || self.wander_timer <= 0.0 {
+            let (min_x, max_x, min_y, max_y) = playable_area.bounds();
+            let mut rng = rand::thread_rng();
+            // an empty playable area (e.g. before it's set up) would make gen_range panic
+            let x = if max_x > min_x { rng.gen_range(min_x, max_x) } else { current_x };
+            let y = if max_y > min_y { rng.gen_range(min_y, max_y) } else { current_y };
+            self.wander_target = Some((x, y));
+            self.wander_timer = interval;
+        }
+
+        if let Some((x, y)) = self.wander_target {
+            self.move_towards(x, y, current_x, current_y);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn flee(
+        &mut self,
+        distance: f32,
+        interval: f32,
+        time: f32,
+        target_x: f32,
+        target_y: f32,
+        current_x: f32,
+        current_y: f32,
+        playable_area: &PlayableArea,
+    ) {
+        let dx = current_x - target_x;
+        let dy = current_y - target_y;
+
+        if (dx * dx + dy * dy).sqrt() < distance {
+            // forget the wander target so wandering starts fresh once safe
+            self.wander_target = None;
+            self.move_along(dx, dy);
+        } else {
+            self.wander(interval, time, current_x, current_y, playable_area);
+        }
+    }
+
+    // moves sideways around the target, while also moving closer or further
+    // away to stay near `distance`
+    pub fn strafe(&mut self, distance: f32, target_x: f32, target_y: f32, current_x: f32, current_y: f32) {
+        let dx = current_x - target_x;
+        let dy = current_y - target_y;
+        let current_distance = (dx * dx + dy * dy).sqrt();
+
+        if current_distance <= 0.0 {
+            self.move_along(1.0, 0.0);
+            return;
+        }
+
+        // positive when too far away, negative when too close
+        let correction = ((current_distance - distance) / distance.max(1.0)).max(-1.0).min(1.0);
+
+        let (away_x, away_y) = (dx / current_distance, dy / current_distance);
+        let (side_x, side_y) = (-away_y, away_x);
+
+        self.move_along(side_x - away_x * correction, side_y - away_y * correction);
+    }
+
     // the rush strategy should be for picking one direction and then rushing
     pub fn rush_towards(&mut self, target_x: f32, target_y: f32, target_z: f32, current_x: f32, current_y: f32) {
         let player_in_range = (current_x - target_x).abs() <= 150.0 || (current_y - target_y).abs() <= 150.0;
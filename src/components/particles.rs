@@ -0,0 +1,149 @@
+/// Particle emitters and the particles they create. Emitters are authored in
+/// the prefabs and give off a steady stream of particles, while one-off bursts
+/// go through the `ParticleBursts` queue. Particles are plain sprites (the
+/// tinted tile sprite) rendered by `RenderFlat2D` like everything else, and
+/// are hidden and handed back to the pool when they expire.
+use amethyst::{
+    assets::PrefabData,
+    derive::PrefabData,
+    ecs::{storage::DenseVecStorage, Component, Entity, WriteStorage},
+    renderer::palette::Srgba,
+    Error,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::resources::particles::ParticleEffect;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum EmitDirection {
+    // a fixed direction in degrees, where 0 is right and 90 is up
+    Angle(f32),
+    // away from the way the emitter is moving, for engine trails. nothing is
+    // emitted while it's standing still
+    Trailing,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+#[serde(deny_unknown_fields)]
+pub struct Emitter {
+    pub effect: ParticleEffect,
+    // particles per second
+    pub rate: f32,
+    pub direction: EmitDirection,
+
+    // fractions of a particle carried over between frames
+    #[serde(skip)]
+    pending: f32,
+
+    #[serde(skip)]
+    last_position: Option<(f32, f32)>,
+}
+
+impl Emitter {
+    // how many particles to emit this frame, and the angle (in radians) their
+    // cone is centred on
+    pub fn update(&mut self, x: f32, y: f32, time_delta: f32) -> Option<(usize, f32)> {
+        let last_position = self.last_position.replace((x, y));
+
+        let angle = match self.direction {
+            EmitDirection::Angle(degrees) => degrees.to_radians(),
+            EmitDirection::Trailing => {
+                let (last_x, last_y) = last_position?;
+                let (dx, dy) = (x - last_x, y - last_y);
+
+                if dx.abs() < std::f32::EPSILON && dy.abs() < std::f32::EPSILON {
+                    self.pending = 0.0;
+                    return None;
+                }
+                (-dy).atan2(-dx)
+            },
+        };
+
+        self.pending += self.rate * time_delta;
+        let count = self.pending.floor();
+        self.pending -= count;
+
+        Some((count as usize, angle))
+    }
+}
+
+impl Component for Emitter {
+    type Storage = DenseVecStorage<Self>;
+}
+
+fn lerp(start: f32, end: f32, amount: f32) -> f32 {
+    start + (end - start) * amount
+}
+
+// one pooled particle. inactive particles are hidden and waiting in the pool
+pub struct Particle {
+    velocity: (f32, f32),
+    gravity: (f32, f32),
+    age: f32,
+    lifetime: f32,
+    start_color: (f32, f32, f32, f32),
+    end_color: (f32, f32, f32, f32),
+    start_size: f32,
+    end_size: f32,
+    active: bool,
+}
+
+impl Component for Particle {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Particle {
+    // a new particle from `effect`, heading out in the cone around `angle`
+    pub fn new(effect: &ParticleEffect, angle: f32) -> Particle {
+        Particle {
+            velocity: effect.launch_velocity(angle),
+            gravity: effect.gravity,
+            age: 0.0,
+            lifetime: effect.pick_lifetime(),
+            start_color: effect.start_color,
+            end_color: effect.end_color,
+            start_size: effect.start_size,
+            end_size: effect.end_size,
+            active: true,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    // the color and size for how far through its life the particle is
+    pub fn appearance(&self) -> (Srgba, f32) {
+        let amount = if self.lifetime > 0.0 {
+            (self.age / self.lifetime).min(1.0)
+        } else {
+            1.0
+        };
+
+        let color = Srgba::new(
+            lerp(self.start_color.0, self.end_color.0, amount),
+            lerp(self.start_color.1, self.end_color.1, amount),
+            lerp(self.start_color.2, self.end_color.2, amount),
+            lerp(self.start_color.3, self.end_color.3, amount),
+        );
+
+        (color, lerp(self.start_size, self.end_size, amount))
+    }
+
+    // ages the particle and returns how far it moved, or None (and marks the
+    // particle inactive) once it has expired
+    pub fn update(&mut self, time_delta: f32) -> Option<(f32, f32)> {
+        self.age += time_delta;
+        if self.age >= self.lifetime {
+            self.active = false;
+            return None;
+        }
+
+        self.velocity.0 += self.gravity.0 * time_delta;
+        self.velocity.1 += self.gravity.1 * time_delta;
+
+        Some((self.velocity.0 * time_delta, self.velocity.1 * time_delta))
+    }
+}
@@ -0,0 +1,14 @@
+/// Where the level spawned an enemy. Enemies that wander too far out of the
+/// arena are culled back to this point instead of being deleted, since
+/// deleting them would count towards finishing the level.
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+#[derive(Clone, Copy, Debug)]
+pub struct SpawnPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Component for SpawnPoint {
+    type Storage = DenseVecStorage<Self>;
+}
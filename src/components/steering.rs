@@ -0,0 +1,46 @@
+/// Boids-style steering for movers that shouldn't pile on top of each other.
+/// `SteeringSystem` uses this to compute a steering velocity that's added to
+/// whatever the entity's movement strategy picked. Separation pushes nearby
+/// movers apart, while the optional alignment and cohesion weights make
+/// groups move together.
+use amethyst::{
+    assets::PrefabData,
+    derive::PrefabData,
+    ecs::{storage::DenseVecStorage, Component, Entity, WriteStorage},
+    Error,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+#[serde(deny_unknown_fields)]
+pub struct Steering {
+    // movers closer than this (in pixels) push each other apart
+    pub separation_radius: f32,
+    // how hard to push apart, relative to the mover's speed
+    pub separation_weight: f32,
+
+    // the rest are optional in prefabs and default to 0, which leaves flocking
+    // off. movers within this distance count as neighbors for alignment and
+    // cohesion
+    #[serde(default)]
+    pub neighbor_radius: f32,
+    // steer towards the average heading of neighbors
+    #[serde(default)]
+    pub alignment_weight: f32,
+    // steer towards the center of neighbors
+    #[serde(default)]
+    pub cohesion_weight: f32,
+}
+
+impl Steering {
+    // the furthest away any rule looks for neighbors
+    pub fn query_radius(&self) -> f32 {
+        self.separation_radius.max(self.neighbor_radius)
+    }
+}
+
+impl Component for Steering {
+    type Storage = DenseVecStorage<Self>;
+}
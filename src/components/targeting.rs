@@ -0,0 +1,82 @@
+/// Decides which player an enemy chases and aims at. Enemies target the
+/// nearest player by default, but a prefab can assign one to a specific player
+/// with `targeting: Player(1)`. If the assigned player is gone, the enemy
+/// falls back to the nearest one that's left.
+use amethyst::{
+    assets::PrefabData,
+    core::{math::Vector3, Transform},
+    derive::PrefabData,
+    ecs::{
+        storage::{DenseVecStorage, MaskedStorage},
+        Component, Entity, Join, ReadStorage, Storage, WriteStorage,
+    },
+    Error,
+};
+
+use serde::{Deserialize, Serialize};
+
+use std::{cmp::Ordering, ops::Deref};
+
+use crate::entities::player::{Player, PlayerIndex};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+pub enum Targeting {
+    Nearest,
+    // the player index, starting from 0 for player one
+    Player(usize),
+}
+
+impl Default for Targeting {
+    fn default() -> Self {
+        Targeting::Nearest
+    }
+}
+
+impl Component for Targeting {
+    type Storage = DenseVecStorage<Self>;
+}
+
+// each living player's index and position
+pub type PlayerPositions = Vec<(usize, Vector3<f32>)>;
+
+impl Targeting {
+    pub fn pick_target(&self, x: f32, y: f32, players: &[(usize, Vector3<f32>)]) -> Option<Vector3<f32>> {
+        if let Targeting::Player(assigned) = self {
+            let target = players.iter().find(|(index, _)| index == assigned);
+            if let Some((_, position)) = target {
+                return Some(*position);
+            }
+        }
+
+        players
+            .iter()
+            .map(|(_, position)| *position)
+            .min_by(|a, b| {
+                distance_squared(a, x, y)
+                    .partial_cmp(&distance_squared(b, x, y))
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+}
+
+fn distance_squared(position: &Vector3<f32>, x: f32, y: f32) -> f32 {
+    (position.x - x).powi(2) + (position.y - y).powi(2)
+}
+
+// players are deleted when they die, so every player left is a valid target.
+// players without an index (there shouldn't be any) count as player one. the
+// transforms can be read or write storage, for systems that also move things
+pub fn player_positions<D>(
+    players: &ReadStorage<'_, Player>,
+    player_indexes: &ReadStorage<'_, PlayerIndex>,
+    transforms: &Storage<'_, Transform, D>,
+) -> PlayerPositions
+where
+    D: Deref<Target = MaskedStorage<Transform>>,
+{
+    (players, player_indexes.maybe(), transforms)
+        .join()
+        .map(|(_player, index, transform)| (index.map_or(0, |i| i.0), *transform.translation()))
+        .collect()
+}
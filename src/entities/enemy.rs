@@ -7,7 +7,10 @@ use amethyst::{
 
 use serde::{Deserialize, Serialize};
 
-use crate::components::{collider::Collider, launcher::Launcher, movement::Movement};
+use crate::components::{
+    ai::Ai, animation::Animation, boss::Boss, collider::Collider, cooldown::Cooldown, launcher::Launcher,
+    movement::Movement, steering::Steering, targeting::Targeting,
+};
 
 //use log::info;
 
@@ -19,6 +22,12 @@ pub struct EnemyPrefab {
     pub collider: Collider,
     pub movement: Movement,
     pub launcher: Option<Launcher>,
+    pub cooldown: Option<Cooldown>,
+    pub boss: Option<Boss>,
+    pub ai: Option<Ai>,
+    pub steering: Option<Steering>,
+    pub targeting: Option<Targeting>,
+    pub animation: Option<Animation>,
 }
 
 impl<'a> PrefabData<'a> for EnemyPrefab {
@@ -28,6 +37,12 @@ impl<'a> PrefabData<'a> for EnemyPrefab {
         <Collider as PrefabData<'a>>::SystemData,
         <Movement as PrefabData<'a>>::SystemData,
         <Launcher as PrefabData<'a>>::SystemData,
+        <Cooldown as PrefabData<'a>>::SystemData,
+        <Boss as PrefabData<'a>>::SystemData,
+        <Ai as PrefabData<'a>>::SystemData,
+        <Steering as PrefabData<'a>>::SystemData,
+        <Targeting as PrefabData<'a>>::SystemData,
+        <Animation as PrefabData<'a>>::SystemData,
     );
 
     fn add_to_entity(
@@ -45,6 +60,24 @@ impl<'a> PrefabData<'a> for EnemyPrefab {
             .add_to_entity(entity, &mut system_data.2, entities, children)?;
         self.launcher
             .add_to_entity(entity, &mut system_data.3, entities, children)?;
+        // launchers only fire when their cooldown allows it, so a launcher
+        // without a cooldown in the prefab gets the default one
+        let cooldown = match (&self.launcher, self.cooldown) {
+            (Some(_), None) => Some(Cooldown::default()),
+            (_, cooldown) => cooldown,
+        };
+        cooldown.add_to_entity(entity, &mut system_data.4, entities, children)?;
+        // boss phases are measured against the health the boss spawns with
+        let boss = self.boss.as_ref().map(|boss| boss.with_max_health(self.enemy.health));
+        boss.add_to_entity(entity, &mut system_data.5, entities, children)?;
+        self.ai
+            .add_to_entity(entity, &mut system_data.6, entities, children)?;
+        self.steering
+            .add_to_entity(entity, &mut system_data.7, entities, children)?;
+        self.targeting
+            .add_to_entity(entity, &mut system_data.8, entities, children)?;
+        self.animation
+            .add_to_entity(entity, &mut system_data.9, entities, children)?;
         Ok(())
     }
 }
@@ -56,6 +89,13 @@ impl<'a> PrefabData<'a> for EnemyPrefab {
 #[serde(deny_unknown_fields)]
 pub struct Enemy {
     pub health: f32,
+    // score for the player that finishes this enemy off
+    #[serde(default = "default_points")]
+    pub points: u32,
+}
+
+fn default_points() -> u32 {
+    100
 }
 
 impl Enemy {
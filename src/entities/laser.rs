@@ -1,74 +1,29 @@
-/// This module includes the laser component creation, laser entity,
-/// and the Direction enum used for rotating the sprite and determining
-/// velocity.
-/// If we need to reuse Direction it can be turned into a separate component.
+/// This module includes the laser component creation and laser entity.
+/// Lasers travel along a unit vector, so they can be fired at any angle
+/// (keyboard input still produces the usual eight directions), and the
+/// sprite rotation is derived from that vector.
 use amethyst::{
-    core::Transform,
-    ecs::prelude::{Component, DenseVecStorage, Entities, Entity, LazyUpdate, ReadExpect},
+    core::{math::Vector2, Transform},
+    ecs::prelude::{Component, DenseVecStorage, WriteStorage},
     renderer::{sprite::SpriteSheetHandle, SpriteRender},
 };
 
-use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+use std::f32::consts::FRAC_PI_2;
 
-use crate::components::cleanup::CleanupTag;
+use crate::{
+    components::{animation::Animation, collider::Collider},
+    resources::pool::{Pool, ShotStorages},
+};
 
 //use log::info;
 
-#[derive(Debug)]
-pub enum Direction {
-    Left,
-    Up,
-    LeftUp,
-    LeftDown,
-    Right,
-    Down,
-    RightUp,
-    RightDown,
-}
-
-use self::Direction::*;
-
-impl Direction {
-    // the system part of the ECS will receive pos/neg horizontal and pos/neg vertical
-    // input from the player. on a keyboard the values are typically:
-    // negative: -1.0
-    // neutral:   0.0
-    // positive:  1.0
-    // an analog stick may provide varying degrees of pos/neg, but it should not affect
-    // this game.
-    fn horizontal(x: f32) -> Option<Direction> {
-        if x < 0.0 {
-            Some(Left)
-        } else if x > 0.0 {
-            Some(Right)
-        } else {
-            None
-        }
-    }
-
-    fn vertical(y: f32) -> Option<Direction> {
-        if y < 0.0 {
-            Some(Down)
-        } else if y > 0.0 {
-            Some(Up)
-        } else {
-            None
-        }
-    }
-
-    // this approach is a little messy. we're assuming it's only ever used
-    // to combine horizontal with vertical. if it's not used that way then it's
-    // meaningless, because we can't have RightRight, or RightLeft, and so on.
-    fn combine(self, other: &Option<Direction>) -> Direction {
-        match (self, other) {
-            (Right, Some(Up)) => RightUp,
-            (Right, Some(Down)) => RightDown,
-            (Left, Some(Up)) => LeftUp,
-            (Left, Some(Down)) => LeftDown,
-            (x, _) => x,
-        }
-    }
-}
+// lasers don't come from a prefab, so their collider is defined here instead.
+// it's the pixel width 7 and height 1, both scaled by 5, and then divided in
+// two to get the half length
+pub const LASER_COLLIDER: Collider = Collider {
+    half_width: 17.5,
+    half_height: 2.5,
+};
 
 /// This is the laser component type, used by `spawn_laser` to create new
 /// laser entities.
@@ -78,35 +33,54 @@ impl Direction {
 /// and eventually destroying it. It will eventually be used for collision detection.
 #[derive(Debug)]
 pub struct Laser {
-    pub direction: Direction,
+    // always a unit vector
+    pub direction: Vector2<f32>,
     pub speed: f32,
+    // the index of the player that fired this laser, who gets the points for
+    // anything it kills
+    pub owner: usize,
 }
 
 impl Laser {
-    pub fn new(direction: Direction, speed: f32) -> Laser {
-        Laser { direction, speed }
+    pub fn new(direction: Vector2<f32>, speed: f32, owner: usize) -> Laser {
+        Laser {
+            direction: direction.normalize(),
+            speed,
+            owner,
+        }
     }
 
-    // we're receiving two types of inputs that may or may not be directional.
-    // we need to decide if they are directional (e.g. Up, or Right and Up),
-    // combine the horizontal and vertical directions if possible, and finally
-    // create a new laser.
-    pub fn from_coordinates(x: Option<f32>, y: Option<f32>, speed: f32) -> Option<Laser> {
-        // inputs come from the amethyst input manager
-        let maybe_x = Direction::horizontal(x.unwrap_or(0.0));
-        let maybe_y = Direction::vertical(y.unwrap_or(0.0));
-
-        // if there's input on the horizontal axis, try to combine it with any vertical
-        // input, otherwise use any vertical input
-        let maybe_composite = maybe_x.map(|x_dir| x_dir.combine(&maybe_y)).or(maybe_y);
-
-        // once we have determined the one true direction or no
-        // direction at all, we can return our Option<Laser>
-        match maybe_composite {
-            Some(dir) => Some(Laser::new(dir, speed)),
-            _ => None,
+    // the system part of the ECS will receive pos/neg horizontal and pos/neg vertical
+    // input from the player. on a keyboard the values are typically:
+    // negative: -1.0
+    // neutral:   0.0
+    // positive:  1.0
+    // so keyboard input can only produce eight directions, while an analog stick
+    // can aim anywhere. if there's no input at all, there's no laser.
+    pub fn from_coordinates(x: Option<f32>, y: Option<f32>, speed: f32, owner: usize) -> Option<Laser> {
+        let direction = Vector2::new(x.unwrap_or(0.0), y.unwrap_or(0.0));
+
+        if direction.x == 0.0 && direction.y == 0.0 {
+            None
+        } else {
+            Some(Laser::new(direction, speed, owner))
         }
     }
+
+    // how far the laser moves each second. lasers cover `speed` along each
+    // axis they're fired on, like the old eight-way lasers, so diagonal shots
+    // from the keyboard are still faster than straight ones. the direction is
+    // scaled so its longest component is 1, which is what keyboard input gives
+    pub fn velocity(&self) -> Vector2<f32> {
+        let longest = self.direction.x.abs().max(self.direction.y.abs());
+        self.direction * (self.speed / longest)
+    }
+
+    // the laser sprite points up when it isn't rotated, so the rotation is
+    // the direction's angle minus a quarter turn
+    pub fn rotation(&self) -> f32 {
+        self.direction.y.atan2(self.direction.x) - FRAC_PI_2
+    }
 }
 
 impl Component for Laser {
@@ -114,45 +88,76 @@ impl Component for Laser {
 }
 
 // this is used by systems/player.rs to create lasers whenever the player fires
-// them. the lazy_update usage is from the space-menace game example and may
-// not be required.
-// BIG TODO: we should not be borrowing the sprite sheet handle from the player.
-// it should be available as a separate game resource and used as a prefab or
-// otherwise found in the world. this implementation ties the laser image to
-// the sprite sheet being used by the player.
+// them. lasers come from a pool, so a laser that's already been recycled is
+// reused (keeping its sprite and cleanup tag) whenever there is one. the laser
+// image comes from the player sprite sheet in `GameplayHandles`. returns whether
+// the laser was fired
 pub fn spawn_laser(
-    sprite_sheet_handle: SpriteSheetHandle,
+    sprite_sheet_handle: &SpriteSheetHandle,
+    animation: Option<&Animation>,
     laser: Laser,
     player_transform: &Transform,
-    entities: &Entities,
-    lazy_update: &ReadExpect<LazyUpdate>,
-) {
+    lasers: &mut WriteStorage<Laser>,
+    pool: &mut Pool<Laser>,
+    shots: &mut ShotStorages,
+) -> bool {
     // an incorrect sprite number here will lead to a memory leak
     let sprite_render = SpriteRender {
-        sprite_sheet: sprite_sheet_handle,
+        sprite_sheet: sprite_sheet_handle.clone(),
         sprite_number: 2,
     };
 
     let mut transform = player_transform.clone();
+    transform.set_rotation_2d(laser.rotation());
+
+    // if the pool is at its cap the shot is skipped
+    match shots.acquire(pool, transform, &sprite_render, animation) {
+        Some((laser_entity, _reused)) => {
+            lasers.insert(laser_entity, laser).expect("unable to fire laser");
+            true
+        },
+        None => false,
+    }
+}
 
-    // the rotation API uses radians. these values were calculated in python
-    // with `rad = lambda x: (x * math.pi) / 180` and then passing in degrees
-    // (e.g. `rad(90)`)
-    match laser.direction {
-        Up => transform.set_rotation_2d(0.0),
-        RightUp => transform.set_rotation_2d(-FRAC_PI_4),
-        Left => transform.set_rotation_2d(FRAC_PI_2),
-        LeftUp => transform.set_rotation_2d(FRAC_PI_4),
-        Down => transform.set_rotation_2d(PI),
-        LeftDown => transform.set_rotation_2d(2.356_194_5),
-        Right => transform.set_rotation_2d(-FRAC_PI_2),
-        RightDown => transform.set_rotation_2d(-2.356_194_5),
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f32::consts::SQRT_2;
+
+    const SPEED: f32 = 100.0;
 
-    let laser_entity: Entity = entities.create();
-    let cleanup_tag = CleanupTag {};
-    lazy_update.insert(laser_entity, laser);
-    lazy_update.insert(laser_entity, cleanup_tag);
-    lazy_update.insert(laser_entity, transform);
-    lazy_update.insert(laser_entity, sprite_render);
+    fn keyboard_laser(x: f32, y: f32) -> Laser {
+        Laser::from_coordinates(Some(x), Some(y), SPEED, 0).unwrap()
+    }
+
+    #[test]
+    fn no_input_fires_nothing() {
+        assert!(Laser::from_coordinates(Some(0.0), Some(0.0), SPEED, 0).is_none());
+        assert!(Laser::from_coordinates(None, None, SPEED, 0).is_none());
+    }
+
+    #[test]
+    fn keyboard_directions_keep_their_speeds() {
+        // (x input, y input, expected speed)
+        let directions = [
+            (0.0, 1.0, SPEED),
+            (0.0, -1.0, SPEED),
+            (-1.0, 0.0, SPEED),
+            (1.0, 0.0, SPEED),
+            (1.0, 1.0, SPEED * SQRT_2),
+            (-1.0, 1.0, SPEED * SQRT_2),
+            (-1.0, -1.0, SPEED * SQRT_2),
+            (1.0, -1.0, SPEED * SQRT_2),
+        ];
+
+        for &(x, y, speed) in directions.iter() {
+            let velocity = keyboard_laser(x, y).velocity();
+
+            assert!((velocity.x - x * SPEED).abs() < 1e-3, "x velocity for ({}, {})", x, y);
+            assert!((velocity.y - y * SPEED).abs() < 1e-3, "y velocity for ({}, {})", x, y);
+            assert!((velocity.norm() - speed).abs() < 1e-3, "speed for ({}, {})", x, y);
+        }
+    }
 }
@@ -2,12 +2,13 @@ use amethyst::{
     assets::PrefabData,
     derive::PrefabData,
     ecs::{storage::DenseVecStorage, Component, Entity, WriteStorage},
+    renderer::{palette::Srgba, resources::Tint},
     Error,
 };
 
 use serde::{Deserialize, Serialize};
 
-use crate::components::collider::Collider;
+use crate::components::{animation::Animation, collider::Collider, cooldown::Cooldown, particles::Emitter};
 
 // this entity is a grouping of components, which allows the prefab loads to aggregate
 // components from a config file (`prefabs/enemy.ron` in our case)
@@ -15,6 +16,9 @@ use crate::components::collider::Collider;
 pub struct PlayerPrefab {
     pub player: Player,
     pub player_collider: Collider,
+    pub player_cooldown: Cooldown,
+    pub animation: Option<Animation>,
+    pub engine_trail: Option<Emitter>,
 }
 
 impl<'a> PrefabData<'a> for PlayerPrefab {
@@ -22,6 +26,9 @@ impl<'a> PrefabData<'a> for PlayerPrefab {
     type SystemData = (
         <Player as PrefabData<'a>>::SystemData,
         <Collider as PrefabData<'a>>::SystemData,
+        <Cooldown as PrefabData<'a>>::SystemData,
+        <Animation as PrefabData<'a>>::SystemData,
+        <Emitter as PrefabData<'a>>::SystemData,
     );
 
     fn add_to_entity(
@@ -35,19 +42,48 @@ impl<'a> PrefabData<'a> for PlayerPrefab {
             .add_to_entity(entity, &mut system_data.0, entities, children)?;
         self.player_collider
             .add_to_entity(entity, &mut system_data.1, entities, children)?;
+        self.player_cooldown
+            .add_to_entity(entity, &mut system_data.2, entities, children)?;
+        self.animation
+            .add_to_entity(entity, &mut system_data.3, entities, children)?;
+        self.engine_trail
+            .add_to_entity(entity, &mut system_data.4, entities, children)?;
         Ok(())
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PrefabData)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PrefabData)]
 #[prefab(Component)]
 #[serde(deny_unknown_fields)]
 pub struct Player {
     pub speed: f32,
     pub laser_speed: f32,
-    // time to delay laser shots in seconds
-    pub fire_delay: f32,
-    pub seconds_since_firing: f32,
+
+    // only hazards wear this down. getting hit by an enemy or a projectile
+    // is still instant death
+    #[serde(default = "default_health")]
+    pub health: f32,
+
+    // set by `HazardSystem` each frame while the player is in a slow zone
+    #[serde(skip, default = "full_speed")]
+    pub speed_scale: f32,
+
+    // true while standing on a teleporter pad, so arriving on the other pad
+    // doesn't send the player straight back
+    #[serde(skip)]
+    pub on_teleporter: bool,
+
+    // set by `PlayerSystem` while there's movement input, for the Move animation
+    #[serde(skip)]
+    pub moving: bool,
+}
+
+fn default_health() -> f32 {
+    100.0
+}
+
+fn full_speed() -> f32 {
+    1.0
 }
 
 impl Player {
@@ -55,24 +91,31 @@ impl Player {
     // also have the player track momentum to compute a speed, but it seems
     // unnecessary
     pub fn get_speed(&self) -> f32 {
-        self.speed
-    }
-
-    // checks if we've had enough time elapse since the last laser
-    // and resets the timer. this is possibly a surprising API for a
-    // `bool` check, but it also ensures we don't rely on calling code
-    // to manage the timer.
-    pub fn can_fire(&mut self, time: f32) -> bool {
-        if self.seconds_since_firing >= self.fire_delay {
-            self.seconds_since_firing = 0.0;
-            true
-        } else {
-            self.seconds_since_firing += time;
-            false
-        }
+        self.speed * self.speed_scale
     }
 }
 
 impl Component for Player {
     type Storage = DenseVecStorage<Self>;
 }
+
+// which player this is, starting from 0 for player one. every player shares
+// the same prefab, so this is added when the level spawns the player instead
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PlayerIndex(pub usize);
+
+impl Component for PlayerIndex {
+    type Storage = DenseVecStorage<Self>;
+}
+
+// players share a sprite, so everyone after player one gets tinted to tell
+// them apart. the colorblind palette avoids telling them apart by red and green
+pub fn player_tint(index: usize, colorblind: bool) -> Tint {
+    match (index, colorblind) {
+        (0, _) => Tint(Srgba::new(1.0, 1.0, 1.0, 1.0)),
+        (1, false) => Tint(Srgba::new(0.45, 0.75, 1.0, 1.0)),
+        (_, false) => Tint(Srgba::new(0.6, 1.0, 0.5, 1.0)),
+        (1, true) => Tint(Srgba::new(0.35, 0.7, 0.9, 1.0)),
+        (_, true) => Tint(Srgba::new(0.95, 0.9, 0.25, 1.0)),
+    }
+}
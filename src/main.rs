@@ -10,15 +10,25 @@ use amethyst::{
         types::DefaultBackend,
         RenderingBundle,
     },
+    ui::{RenderUi, UiBundle},
     utils::application_root_dir,
+    window::DisplayConfig,
 };
 
+#[cfg(feature = "debug-tools")]
+use amethyst::renderer::plugins::RenderDebugLines;
+
 mod components;
 mod entities;
 mod resources;
 mod states;
 mod systems;
 use entities::{enemy::EnemyPrefab, player::PlayerPrefab};
+use resources::{
+    audio::{AudioConfig, Music, SoundQueue},
+    controls::ControlsConfig,
+    settings::{Settings, UserSettings},
+};
 
 fn main() -> amethyst::Result<()> {
     amethyst::start_logger(Default::default());
@@ -26,28 +36,82 @@ fn main() -> amethyst::Result<()> {
     let app_root = application_root_dir()?;
 
     let assets = app_root.join("assets");
-    let display_config = app_root.join("config").join("display_config.ron");
-    let binding_path = app_root.join("config").join("bindings.ron");
+    let config_dir = app_root.join("config");
+    let display_config_path = config_dir.join("display_config.ron");
+
+    // the controls config picks a bindings profile, and any bindings the
+    // player saved in game take priority over the shipped defaults
+    let mut controls_config = ControlsConfig::load(config_dir.join("controls.ron")).unwrap_or_default();
+    controls_config.check_profile();
+    let binding_path = controls_config.bindings_path(&config_dir);
 
     // load all the levels
-    let level_config = app_root.join("config").join("levels.ron");
+    let level_config = config_dir.join("levels.ron");
     let levels = resources::level::LevelConfig::load(&level_config).unwrap();
     let all_levels = resources::level::get_all_levels(levels);
 
+    // laser and projectile animations, since they don't have prefabs
+    let shot_animations =
+        resources::animations::ShotAnimations::load(config_dir.join("animations.ron")).unwrap_or_default();
+
+    // explosions, laser impacts and the other particle bursts
+    let particle_effects = resources::particles::ParticleEffects::load(config_dir.join("particles.ron")).unwrap();
+
+    // how many lasers and projectiles can be on screen at once
+    let pool_config = resources::pool::PoolConfig::load(config_dir.join("pools.ron")).unwrap_or_default();
+
+    // volumes, and whether to open an audio device at all
+    let mut audio_config = AudioConfig::load(config_dir.join("audio.ron")).unwrap_or_default();
+
+    // settings saved from the options menu take priority over the shipped
+    // display and audio configs
+    let mut display_config = DisplayConfig::load(&display_config_path)?;
+    let user_settings = UserSettings::load(Settings::user_settings_path(&config_dir)).unwrap_or_default();
+    let mut settings = Settings::new(&display_config, &audio_config);
+    settings.merge(&user_settings);
+    settings.apply_display(&mut display_config);
+    settings.apply_audio(&mut audio_config);
+    let (frame_limit_strategy, max_fps) = settings.frame_limit();
+
     let input_bundle = InputBundle::<StringBindings>::new().with_bindings_from_file(binding_path)?;
 
+    let rendering_bundle = RenderingBundle::<DefaultBackend>::new()
+        .with_plugin(RenderToWindow::from_config(display_config).with_clear([0.0, 0.0, 0.0, 1.0]))
+        .with_plugin(RenderFlat2D::default())
+        .with_plugin(RenderUi::default());
+
+    // the collider overlay is a developer tool, so only render debug lines
+    // when it's compiled in
+    #[cfg(feature = "debug-tools")]
+    let rendering_bundle = rendering_bundle.with_plugin(RenderDebugLines::default());
+
     let game_data = GameDataBuilder::default()
         .with_bundle(TransformBundle::new())?
         .with_bundle(input_bundle)?
+        .with_bundle(UiBundle::<StringBindings>::new())?
         .with_system_desc(PrefabLoaderSystemDesc::<EnemyPrefab>::default(), "", &[])
         .with_system_desc(PrefabLoaderSystemDesc::<PlayerPrefab>::default(), "", &[])
-        .with_bundle(
-            RenderingBundle::<DefaultBackend>::new()
-                .with_plugin(RenderToWindow::from_config_path(display_config)?.with_clear([0.0, 0.0, 0.0, 1.0]))
-                .with_plugin(RenderFlat2D::default()),
-        )?;
+        .with_bundle(rendering_bundle)?;
+
+    // plays on the default output device. without one (or with a null output)
+    // the game runs silently
+    let game_data = game_data.with_bundle(systems::SoundBundle {
+        null_output: audio_config.null_output,
+    })?;
 
-    let mut game = Application::new(assets, states::GameplayState::new(all_levels), game_data)?;
+    // the main menu starts each new game from this resource
+    let mut game = Application::build(assets, states::MainMenuState::default())?
+        .with_resource(all_levels)
+        .with_resource(controls_config)
+        .with_resource(shot_animations)
+        .with_resource(particle_effects)
+        .with_resource(pool_config)
+        .with_resource(audio_config)
+        .with_resource(settings)
+        .with_resource(Music::default())
+        .with_resource(SoundQueue::default())
+        .with_frame_limit(frame_limit_strategy, max_fps)
+        .build(game_data)?;
     game.run();
 
     Ok(())
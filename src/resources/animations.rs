@@ -0,0 +1,17 @@
+/// Animations for entities that don't come from a prefab, loaded from
+/// `config/animations.ron`. Lasers and projectiles are pooled (see
+/// `resources::pool`), so new shots get a copy of these when they're created.
+use serde::{Deserialize, Serialize};
+
+use crate::components::animation::Animation;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShotAnimations {
+    // frames from the player sprite sheet
+    #[serde(default)]
+    pub laser: Option<Animation>,
+    // frames from the enemy sprite sheet
+    #[serde(default)]
+    pub projectile: Option<Animation>,
+}
@@ -0,0 +1,159 @@
+/// Sound effects and music. Systems queue effects with `SoundQueue` and
+/// `SoundSystem` plays them, while each level names the music track it loops
+/// (see `config/levels.ron`). Without an audio device, or with `null_output`
+/// set in `config/audio.ron`, nothing is played and the game runs the same.
+use amethyst::{
+    assets::{AssetStorage, Loader, ProgressCounter},
+    audio::{output::Output, AudioSink, Source, SourceHandle, WavFormat},
+    prelude::*,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AudioConfig {
+    // each goes from 0.0 (silent) to 1.0. music and sound effects are both
+    // scaled by the master volume
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+
+    // never opens an audio device, for machines that don't have one (like
+    // test runners)
+    #[serde(default)]
+    pub null_output: bool,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        AudioConfig {
+            master_volume: 1.0,
+            music_volume: 0.5,
+            sfx_volume: 0.8,
+            null_output: false,
+        }
+    }
+}
+
+impl AudioConfig {
+    pub fn music_level(&self) -> f32 {
+        (self.master_volume * self.music_volume).max(0.0).min(1.0)
+    }
+
+    pub fn sfx_level(&self) -> f32 {
+        (self.master_volume * self.sfx_volume).max(0.0).min(1.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SoundEffect {
+    LaserFire,
+    EnemyHit,
+    EnemyDeath,
+    PlayerHit,
+    // loaded with the rest, but there are no pickups in the game yet, so
+    // nothing plays it
+    #[allow(dead_code)]
+    Pickup,
+    LevelTransition,
+}
+
+/// Handles for every sound effect, loaded with the rest of the gameplay assets
+/// and kept as a world resource next to `GameplayHandles`
+#[derive(Clone)]
+pub struct SoundHandles {
+    pub laser_fire: SourceHandle,
+    pub enemy_hit: SourceHandle,
+    pub enemy_death: SourceHandle,
+    pub player_hit: SourceHandle,
+    pub pickup: SourceHandle,
+    pub level_transition: SourceHandle,
+}
+
+impl SoundHandles {
+    pub fn get(&self, effect: SoundEffect) -> &SourceHandle {
+        match effect {
+            SoundEffect::LaserFire => &self.laser_fire,
+            SoundEffect::EnemyHit => &self.enemy_hit,
+            SoundEffect::EnemyDeath => &self.enemy_death,
+            SoundEffect::PlayerHit => &self.player_hit,
+            SoundEffect::Pickup => &self.pickup,
+            SoundEffect::LevelTransition => &self.level_transition,
+        }
+    }
+}
+
+pub fn get_sound_handles(world: &mut World, progress_counter: &mut ProgressCounter) -> SoundHandles {
+    SoundHandles {
+        laser_fire: load_sound(world, "audio/laser_fire.wav", progress_counter),
+        enemy_hit: load_sound(world, "audio/enemy_hit.wav", progress_counter),
+        enemy_death: load_sound(world, "audio/enemy_death.wav", progress_counter),
+        player_hit: load_sound(world, "audio/player_hit.wav", progress_counter),
+        pickup: load_sound(world, "audio/pickup.wav", progress_counter),
+        level_transition: load_sound(world, "audio/level_transition.wav", progress_counter),
+    }
+}
+
+// helper for loading a wav file from the assets directory
+fn load_sound(world: &World, path: &str, progress_counter: &mut ProgressCounter) -> SourceHandle {
+    let loader = world.read_resource::<Loader>();
+    loader.load(
+        path,
+        WavFormat,
+        progress_counter,
+        &world.read_resource::<AssetStorage<Source>>(),
+    )
+}
+
+// sound effects requested this frame
+#[derive(Default)]
+pub struct SoundQueue {
+    requests: Vec<SoundEffect>,
+}
+
+impl SoundQueue {
+    pub fn play(&mut self, effect: SoundEffect) {
+        self.requests.push(effect);
+    }
+
+    pub fn drain(&mut self) -> Vec<SoundEffect> {
+        std::mem::take(&mut self.requests)
+    }
+}
+
+/// The track that's playing. `DjSystem` asks for the next track whenever the
+/// audio sink runs dry, and gets the same one back, so tracks loop.
+#[derive(Default)]
+pub struct Music {
+    track: Option<String>,
+    handle: Option<SourceHandle>,
+}
+
+impl Music {
+    pub fn next_track(&mut self) -> Option<SourceHandle> {
+        self.handle.clone()
+    }
+}
+
+// switches to `track` (the name of a wav file in `assets/audio/music`) unless
+// it's already playing. None stops the music
+pub fn play_music(world: &mut World, track: Option<&str>) {
+    if world.read_resource::<Music>().track.as_deref() == track {
+        return;
+    }
+
+    let handle = track.map(|name| load_sound(world, &format!("audio/music/{}.wav", name), &mut ProgressCounter::new()));
+
+    // dropping the old sink stops whatever it was playing, and `DjSystem`
+    // starts the new track once it finds the new sink empty
+    let sink = world.try_fetch::<Output>().map(|output| AudioSink::new(&output));
+    if let Some(mut sink) = sink {
+        sink.set_volume(world.read_resource::<AudioConfig>().music_level());
+        world.insert(sink);
+    }
+
+    let mut music = world.write_resource::<Music>();
+    music.track = track.map(String::from);
+    music.handle = handle;
+}
@@ -0,0 +1,136 @@
+/// Controls configuration loaded from `config/controls.ron`. This picks the
+/// bindings profile (keyboard or gamepad) and the deadzone used for analog
+/// input. Rebinding controls in game writes a user bindings file next to
+/// the shipped defaults, and that file wins whenever it exists.
+use amethyst::{
+    core::{
+        math::{Point3, Vector2},
+        Transform,
+    },
+    renderer::Camera,
+    window::ScreenDimensions,
+};
+
+use serde::{Deserialize, Serialize};
+
+use std::path::{Path, PathBuf};
+
+use log::warn;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum ControlProfile {
+    Keyboard,
+    // requires building with the `gamepad` feature
+    Gamepad,
+}
+
+// how the laser direction is chosen
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum AimMode {
+    // the `x_laser` and `y_laser` axes (arrow keys or the right stick)
+    Axes,
+    // aim at the cursor and hold the `fire` action (a mouse button by default).
+    // there's only one cursor, so only player one aims this way
+    Mouse,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ControlsConfig {
+    pub profile: ControlProfile,
+    pub aim_mode: AimMode,
+    // analog input with a magnitude below this is treated as no input
+    pub deadzone: f32,
+}
+
+impl Default for ControlsConfig {
+    fn default() -> Self {
+        ControlsConfig {
+            profile: ControlProfile::Keyboard,
+            aim_mode: AimMode::Axes,
+            deadzone: 0.2,
+        }
+    }
+}
+
+impl ControlsConfig {
+    fn default_bindings_file(&self) -> &'static str {
+        match self.profile {
+            ControlProfile::Keyboard => "bindings.ron",
+            ControlProfile::Gamepad => "bindings_gamepad.ron",
+        }
+    }
+
+    // where in-game rebinding saves bindings for the current profile
+    pub fn user_bindings_path(&self, config_dir: &Path) -> PathBuf {
+        config_dir.join(format!("user_{}", self.default_bindings_file()))
+    }
+
+    // the user's bindings if they've saved any, otherwise the shipped defaults
+    pub fn bindings_path(&self, config_dir: &Path) -> PathBuf {
+        let user_path = self.user_bindings_path(config_dir);
+        if user_path.exists() {
+            user_path
+        } else {
+            config_dir.join(self.default_bindings_file())
+        }
+    }
+
+    // the gamepad profile needs SDL, which is only compiled in with the
+    // `gamepad` feature. without it the keyboard profile is used instead
+    pub fn check_profile(&mut self) {
+        if let ControlProfile::Gamepad = self.profile {
+            if !cfg!(feature = "gamepad") {
+                warn!("the gamepad profile needs the \"gamepad\" feature, using the keyboard profile instead");
+                self.profile = ControlProfile::Keyboard;
+            }
+        }
+    }
+
+    // uses a radial deadzone so diagonal stick input isn't cut off early, and
+    // rescales the rest of the stick's range so input still goes smoothly from
+    // 0 to 1. this is the only deadzone (the gamepad bindings don't set one).
+    // keyboard input is always -1, 0, or 1 and passes through unchanged
+    pub fn apply_deadzone(&self, x: f32, y: f32) -> (f32, f32) {
+        let magnitude = (x * x + y * y).sqrt();
+        if magnitude < self.deadzone || magnitude == 0.0 {
+            return (0.0, 0.0);
+        }
+
+        if magnitude >= 1.0 {
+            return (x, y);
+        }
+
+        let scale = (magnitude - self.deadzone) / (1.0 - self.deadzone) / magnitude;
+        (x * scale, y * scale)
+    }
+}
+
+// converts a screen position (e.g. from `InputHandler::mouse_position`) into
+// world coordinates using the gameplay camera
+pub fn screen_to_world(
+    screen_x: f32,
+    screen_y: f32,
+    dimensions: &ScreenDimensions,
+    camera: &Camera,
+    camera_transform: &Transform,
+) -> (f32, f32) {
+    let screen_diagonal = Vector2::new(dimensions.width(), dimensions.height());
+    let point = camera.projection().screen_to_world_point(
+        Point3::new(screen_x, screen_y, 0.0),
+        screen_diagonal,
+        camera_transform,
+    );
+
+    (point.x, point.y)
+}
+
+// player one uses the plain binding names from `config/bindings.ron`, and
+// other players add a suffix, e.g. `x_axis_p2` for player two
+pub fn player_binding(name: &str, index: usize) -> String {
+    if index == 0 {
+        name.to_string()
+    } else {
+        format!("{}_p{}", name, index + 1)
+    }
+}
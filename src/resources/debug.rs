@@ -0,0 +1,37 @@
+/// Developer-only toggles for the `debug-tools` cargo feature. This module
+/// is compiled out of shipping builds, so gameplay code should never depend
+/// on anything here without a matching `#[cfg(feature = "debug-tools")]`.
+
+// how much to slow the game down when slow motion is toggled on
+pub const SLOW_MOTION_SCALE: f32 = 0.25;
+
+#[derive(Debug, Default)]
+pub struct DebugTools {
+    // when true the player can't be hurt by enemies or projectiles
+    pub god_mode: bool,
+    pub slow_motion: bool,
+    // when true `DebugOverlaySystem` draws collider bounds and arena limits
+    pub show_overlay: bool,
+}
+
+impl DebugTools {
+    pub fn toggle_god_mode(&mut self) -> bool {
+        self.god_mode = !self.god_mode;
+        self.god_mode
+    }
+
+    pub fn toggle_overlay(&mut self) -> bool {
+        self.show_overlay = !self.show_overlay;
+        self.show_overlay
+    }
+
+    // returns the time scale the caller should apply to the `Time` resource
+    pub fn toggle_slow_motion(&mut self) -> f32 {
+        self.slow_motion = !self.slow_motion;
+        if self.slow_motion {
+            SLOW_MOTION_SCALE
+        } else {
+            1.0
+        }
+    }
+}
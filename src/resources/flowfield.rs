@@ -0,0 +1,148 @@
+/// Pathfinding for enemies chasing players around walls. A flow field holds
+/// the distance from every cell in the level grid to one goal cell, so any
+/// number of enemies can find their next step towards that goal with a quick
+/// lookup. There's one field per cell that a player is standing in, and a
+/// field is only computed when a player moves into a new cell.
+use amethyst::core::math::Vector3;
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::resources::{
+    level::{cell_to_world, world_to_cell, GRID_COLUMNS, GRID_ROWS},
+    walls::Walls,
+};
+
+// the cost of stepping to a neighboring cell. diagonals cost about sqrt(2)
+// times as much as straight steps
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+const NEIGHBORS: [(i32, i32, u32); 8] = [
+    (1, 0, STRAIGHT_COST),
+    (-1, 0, STRAIGHT_COST),
+    (0, 1, STRAIGHT_COST),
+    (0, -1, STRAIGHT_COST),
+    (1, 1, DIAGONAL_COST),
+    (1, -1, DIAGONAL_COST),
+    (-1, 1, DIAGONAL_COST),
+    (-1, -1, DIAGONAL_COST),
+];
+
+const UNREACHABLE: u32 = std::u32::MAX;
+
+struct FlowField {
+    distances: Vec<u32>,
+}
+
+impl FlowField {
+    // dijkstra outwards from the goal
+    fn new(goal: (i32, i32), walls: &Walls) -> FlowField {
+        let mut distances = vec![UNREACHABLE; (GRID_COLUMNS * GRID_ROWS) as usize];
+        let mut frontier = BinaryHeap::new();
+
+        if let Some(index) = cell_index(goal) {
+            distances[index] = 0;
+            frontier.push(Reverse((0, goal)));
+        }
+
+        while let Some(Reverse((distance, cell))) = frontier.pop() {
+            if cell_index(cell).map_or(true, |index| distance > distances[index]) {
+                continue;
+            }
+
+            for (neighbor, cost) in open_neighbors(cell, walls) {
+                let next_distance = distance + cost;
+                if let Some(index) = cell_index(neighbor) {
+                    if next_distance < distances[index] {
+                        distances[index] = next_distance;
+                        frontier.push(Reverse((next_distance, neighbor)));
+                    }
+                }
+            }
+        }
+
+        FlowField { distances }
+    }
+
+    fn distance(&self, cell: (i32, i32)) -> u32 {
+        cell_index(cell).map_or(UNREACHABLE, |index| self.distances[index])
+    }
+
+    // the neighbor of `cell` that gets closest to the goal, if any of them are
+    // closer than `cell` itself
+    fn next_cell(&self, cell: (i32, i32), walls: &Walls) -> Option<(i32, i32)> {
+        open_neighbors(cell, walls)
+            .map(|(neighbor, _cost)| neighbor)
+            .filter(|neighbor| self.distance(*neighbor) < self.distance(cell))
+            .min_by_key(|neighbor| self.distance(*neighbor))
+    }
+}
+
+fn cell_index((column, row): (i32, i32)) -> Option<usize> {
+    if column >= 0 && column < GRID_COLUMNS && row >= 0 && row < GRID_ROWS {
+        Some((row * GRID_COLUMNS + column) as usize)
+    } else {
+        None
+    }
+}
+
+fn is_open(cell: (i32, i32), walls: &Walls) -> bool {
+    cell_index(cell).is_some() && !walls.is_wall_cell(cell)
+}
+
+// neighbors that can be stepped to and their cost. diagonal steps aren't
+// allowed to cut the corner of a wall
+fn open_neighbors(cell: (i32, i32), walls: &Walls) -> impl Iterator<Item = ((i32, i32), u32)> + '_ {
+    NEIGHBORS.iter().filter_map(move |&(dx, dy, cost)| {
+        let neighbor = (cell.0 + dx, cell.1 + dy);
+        let cuts_corner =
+            dx != 0 && dy != 0 && !(is_open((cell.0 + dx, cell.1), walls) && is_open((cell.0, cell.1 + dy), walls));
+
+        if is_open(neighbor, walls) && !cuts_corner {
+            Some((neighbor, cost))
+        } else {
+            None
+        }
+    })
+}
+
+#[derive(Default)]
+pub struct FlowFields {
+    // keyed by goal cell
+    fields: HashMap<(i32, i32), FlowField>,
+}
+
+impl FlowFields {
+    // makes sure there's a field for each cell a player is in, and drops the
+    // ones nobody is standing in anymore
+    pub fn update(&mut self, player_positions: &[(usize, Vector3<f32>)], walls: &Walls) {
+        let goals: Vec<(i32, i32)> = player_positions
+            .iter()
+            .map(|(_index, position)| world_to_cell(position.x, position.y))
+            .collect();
+
+        self.fields.retain(|goal, _field| goals.contains(goal));
+
+        for goal in goals {
+            self.fields.entry(goal).or_insert_with(|| FlowField::new(goal, walls));
+        }
+    }
+
+    // where a mover at (x, y) should head next to reach a target at
+    // (target_x, target_y). with nothing in the way that's the target itself,
+    // otherwise it's the center of the next cell along the flow field
+    pub fn waypoint(&self, x: f32, y: f32, target_x: f32, target_y: f32, walls: &Walls) -> (f32, f32) {
+        if walls.line_of_sight(x, y, target_x, target_y) {
+            return (target_x, target_y);
+        }
+
+        self.fields
+            .get(&world_to_cell(target_x, target_y))
+            .and_then(|field| field.next_cell(world_to_cell(x, y), walls))
+            .map(|(column, row)| cell_to_world(column, row))
+            .unwrap_or((target_x, target_y))
+    }
+}
@@ -17,12 +17,16 @@ pub struct GameplayHandles {
     // image used for fade to black transitions
     pub overlay_sprite_handle: Handle<SpriteSheet>,
 
+    // a plain white square, tinted for level tiles like hazards
+    pub tile_sprite_handle: Handle<SpriteSheet>,
+
     // handle to clone for the sprite sheet containing enemies
     pub enemy_sprites_handle: Handle<SpriteSheet>,
 
     // all the prefab handles
     pub enemy_prefab_handle: Handle<Prefab<EnemyPrefab>>,
     pub flying_enemy_prefab_handle: Handle<Prefab<EnemyPrefab>>,
+    pub hunter_enemy_prefab_handle: Handle<Prefab<EnemyPrefab>>,
     pub player_prefab_handle: Handle<Prefab<PlayerPrefab>>,
     pub boss_prefab_handle: Handle<Prefab<EnemyPrefab>>,
 
@@ -35,20 +39,24 @@ pub fn get_game_handles(
     progress_counter: &mut ProgressCounter,
     enemy_prefab_handle: Handle<Prefab<EnemyPrefab>>,
     flying_enemy_prefab_handle: Handle<Prefab<EnemyPrefab>>,
+    hunter_enemy_prefab_handle: Handle<Prefab<EnemyPrefab>>,
     player_prefab_handle: Handle<Prefab<PlayerPrefab>>,
     boss_prefab_handle: Handle<Prefab<EnemyPrefab>>,
 ) -> GameplayHandles {
     let background_sprite_handle = load_sprite_sheet(world, "background", progress_counter);
     let overlay_sprite_handle = load_sprite_sheet(world, "transition", progress_counter);
+    let tile_sprite_handle = load_sprite_sheet(world, "tile", progress_counter);
     let enemy_sprites_handle = load_sprite_sheet(world, "enemy_sprites", progress_counter);
     let player_sprites_handle = load_sprite_sheet(world, "sprite_sheet", progress_counter);
 
     GameplayHandles {
         background_sprite_handle,
         overlay_sprite_handle,
+        tile_sprite_handle,
         enemy_sprites_handle,
         enemy_prefab_handle,
         flying_enemy_prefab_handle,
+        hunter_enemy_prefab_handle,
         player_prefab_handle,
         boss_prefab_handle,
         player_sprites_handle,
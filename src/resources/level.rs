@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 /// This represents everything we need to know about one level in order
@@ -6,29 +8,71 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug)]
 pub struct LevelMetadata {
     layout: Vec<EntityRecord>,
+    music: Option<String>,
 }
 
 impl LevelMetadata {
-    pub fn new(layout: Vec<EntityRecord>) -> LevelMetadata {
-        LevelMetadata { layout }
+    pub fn new(layout: Vec<EntityRecord>, music: Option<String>) -> LevelMetadata {
+        LevelMetadata { layout, music }
     }
 
     pub fn get_layout(&self) -> &[EntityRecord] {
         self.layout.as_slice()
     }
+
+    // the music track that loops while this level is played, if any
+    pub fn get_music(&self) -> Option<&str> {
+        self.music.as_deref()
+    }
+}
+
+// how the level being played is going. `CollisionSystem` sets `boss_defeated`
+// when a laser finishes off the boss, which is the only way a boss completes
+// the level (a boss leaving the arena doesn't count)
+#[derive(Debug, Default)]
+pub struct LevelProgress {
+    pub boss_defeated: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct LevelConfig {
     pub rows: Vec<Vec<String>>,
+
+    // the track for each level, in the same order as `rows`. levels past the
+    // end of the list have no music
+    #[serde(default)]
+    pub music: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+// the level grid covers this many world units, and each row has
+// `GRID_COLUMNS` characters. levels should have `GRID_ROWS` rows
+const LEVEL_WIDTH: f32 = 2880.0;
+const LEVEL_HEIGHT: f32 = 1710.0;
+pub const GRID_COLUMNS: i32 = 50;
+pub const GRID_ROWS: i32 = 25;
+const ROW_LENGTH: f32 = GRID_COLUMNS as f32;
+const ROW_COUNT: f32 = GRID_ROWS as f32;
+
+// the size of one character in the level grid, in world units
+pub const CELL_WIDTH: f32 = LEVEL_WIDTH / ROW_LENGTH;
+pub const CELL_HEIGHT: f32 = LEVEL_HEIGHT / ROW_COUNT;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum EntityType {
     FlyingEnemy,
     SquareEnemy,
+    // an enemy driven by the AI state machine in `prefabs/hunter_enemy.ron`
+    HunterEnemy,
     Boss,
-    Player,
+    // the player index, starting from 0 for player one
+    Player(usize),
+    // one cell of solid level geometry
+    Wall,
+    // hazard tiles the player can walk over
+    DamageZone,
+    SlowZone,
+    // a pad that sends the player to the pad it's paired with
+    Teleporter { destination_x: f32, destination_y: f32 },
 }
 
 // entity to create, x coordinate, y coordinate
@@ -39,21 +83,38 @@ pub type Levels = Vec<LevelMetadata>;
 
 // loop through our grid to get a vector containing only entities
 // and transform coordinates and other level
-fn get_level_entities(rows: &mut Vec<String>) -> LevelMetadata {
+fn get_level_entities(rows: &mut Vec<String>, music: Option<String>) -> LevelMetadata {
     // make sure we reverse because y=0 is the bottom of the screen,
     // but the level config is ordered top to bottom
     rows.reverse();
 
     let mut records = Vec::new();
 
+    // teleporter pads by digit, paired up once the whole grid is read
+    let mut pads: HashMap<char, Vec<(f32, f32)>> = HashMap::new();
+
     for (y_index, r) in rows.iter().enumerate() {
-        for (x_index, s) in r.chars().enumerate() {
+        let mut chars = r.chars().enumerate().peekable();
+
+        while let Some((x_index, s)) = chars.next() {
             let entity = match s {
                 'F' => Some(EntityType::FlyingEnemy),
                 'S' => Some(EntityType::SquareEnemy),
+                'H' => Some(EntityType::HunterEnemy),
                 'B' => Some(EntityType::Boss),
-
-                'P' => Some(EntityType::Player),
+                '#' => Some(EntityType::Wall),
+                '^' => Some(EntityType::DamageZone),
+                '~' => Some(EntityType::SlowZone),
+
+                // `P2` takes up two cells, so the 2 is skipped
+                'P' => {
+                    if chars.peek().map(|(_, next)| *next) == Some('2') {
+                        chars.next();
+                        Some(EntityType::Player(1))
+                    } else {
+                        Some(EntityType::Player(0))
+                    }
+                },
                 _ => None,
             };
 
@@ -62,11 +123,36 @@ fn get_level_entities(rows: &mut Vec<String>) -> LevelMetadata {
 
             if let Some(e) = entity {
                 records.push((e, x, y));
+            } else if s.is_ascii_digit() && s != '0' {
+                pads.entry(s).or_insert_with(Vec::new).push((x, y));
             }
         }
     }
 
-    LevelMetadata::new(records)
+    // two pads with the same digit link to each other. a digit used any other
+    // number of times is ignored, since there's no single pad to send it to
+    for positions in pads.values() {
+        if let [(x1, y1), (x2, y2)] = positions.as_slice() {
+            records.push((
+                EntityType::Teleporter {
+                    destination_x: *x2,
+                    destination_y: *y2,
+                },
+                *x1,
+                *y1,
+            ));
+            records.push((
+                EntityType::Teleporter {
+                    destination_x: *x1,
+                    destination_y: *y1,
+                },
+                *x2,
+                *y2,
+            ));
+        }
+    }
+
+    LevelMetadata::new(records, music)
 }
 
 fn get_coordinates(x_grid_pos: usize, y_grid_pos: usize) -> (f32, f32) {
@@ -75,24 +161,38 @@ fn get_coordinates(x_grid_pos: usize, y_grid_pos: usize) -> (f32, f32) {
     // and then multiplies it by width and height of our screen dimensions to pick
     // coordinates usable for transform components
     // these come from ScreenDimensions and should use that resource if possible
-    let width = 2880.0;
-    let height = 1710.0;
-    let str_len = 50.0;
-    let num_rows = 25.0;
-
-    let x = (x_grid_pos as f32 / str_len) * width;
-    let y = (y_grid_pos as f32 / num_rows) * height;
+    let x = (x_grid_pos as f32 / ROW_LENGTH) * LEVEL_WIDTH;
+    let y = (y_grid_pos as f32 / ROW_COUNT) * LEVEL_HEIGHT;
 
     (x, y)
 }
 
-pub fn get_all_levels(mut level_config: LevelConfig) -> Levels {
-    level_config.rows.reverse();
+// the grid cell (column, row) closest to a world position. rows count up from
+// the bottom, like the y axis. positions outside the level give cells outside
+// the grid
+pub fn world_to_cell(x: f32, y: f32) -> (i32, i32) {
+    ((x / CELL_WIDTH).round() as i32, (y / CELL_HEIGHT).round() as i32)
+}
+
+// the world position of a cell, which is where anything spawned there is placed
+pub fn cell_to_world(column: i32, row: i32) -> (f32, f32) {
+    (column as f32 * CELL_WIDTH, row as f32 * CELL_HEIGHT)
+}
+
+pub fn get_all_levels(level_config: LevelConfig) -> Levels {
+    // pair each level with its track before the order changes
+    let mut music = level_config.music.into_iter();
+    let mut levels: Vec<(Vec<String>, Option<String>)> = level_config
+        .rows
+        .into_iter()
+        .map(|level| (level, music.next()))
+        .collect();
+    levels.reverse();
 
     let mut levels_vec = Vec::new();
 
-    for mut level in level_config.rows.iter_mut() {
-        let next_level = get_level_entities(&mut level);
+    for (mut level, track) in levels {
+        let next_level = get_level_entities(&mut level, track);
         levels_vec.push(next_level);
     }
     levels_vec.reverse();
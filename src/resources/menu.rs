@@ -0,0 +1,162 @@
+/// A small keyboard driven menu built from amethyst UI text. It's shared by
+/// any state that needs a list of selectable options (pause, main menu, etc).
+/// The owning state is responsible for calling `delete` when it stops so
+/// the text doesn't linger over the next state.
+use amethyst::{
+    assets::{AssetStorage, Handle, Loader},
+    ecs::prelude::Entity,
+    input::{is_key_down, VirtualKeyCode},
+    prelude::*,
+    ui::{get_default_font, Anchor, FontAsset, LineMode, UiText, UiTransform},
+    winit::Event,
+};
+
+const ITEM_HEIGHT: f32 = 60.0;
+const FONT_SIZE: f32 = 40.0;
+
+// spacing for the header lines above the items
+const HEADER_LINE_HEIGHT: f32 = 70.0;
+const HEADER_GAP: f32 = 120.0;
+
+// longer menus shrink their items to fit in this height
+const MAX_MENU_HEIGHT: f32 = 640.0;
+
+const SELECTED_COLOR: [f32; 4] = [1.0, 0.85, 0.2, 1.0];
+const UNSELECTED_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+pub struct Menu<T> {
+    items: Vec<(T, Entity)>,
+    selected: usize,
+
+    // text that can't be selected, shown above the items
+    header: Vec<Entity>,
+}
+
+impl<T: Copy + PartialEq> Menu<T> {
+    // creates one text entity per item, stacked vertically around the middle
+    // of the screen. the first item starts selected
+    pub fn new(world: &mut World, items: Vec<(T, String)>) -> Menu<T> {
+        Menu::with_header(world, Vec::new(), items)
+    }
+
+    // a menu with lines of text (and their font sizes) stacked above the items,
+    // such as a title or final scores
+    pub fn with_header(world: &mut World, header: Vec<(String, f32)>, items: Vec<(T, String)>) -> Menu<T> {
+        let font = {
+            let loader = world.read_resource::<Loader>();
+            let font_storage = world.read_resource::<AssetStorage<FontAsset>>();
+            get_default_font(&loader, &font_storage)
+        };
+
+        let item_height = ITEM_HEIGHT.min(MAX_MENU_HEIGHT / items.len() as f32);
+        let font_size = FONT_SIZE * item_height / ITEM_HEIGHT;
+        let top = (items.len() as f32 - 1.0) * item_height * 0.5;
+
+        let items = items
+            .into_iter()
+            .enumerate()
+            .map(|(index, (item, label))| {
+                let color = if index == 0 { SELECTED_COLOR } else { UNSELECTED_COLOR };
+                let y = top - index as f32 * item_height;
+                let name = format!("menu_item_{}", index);
+                let entity = create_text(world, &font, name, y, item_height, label, color, font_size);
+                (item, entity)
+            })
+            .collect();
+
+        let header_top = top + HEADER_GAP + HEADER_LINE_HEIGHT * (header.len() as f32 - 1.0);
+
+        let header = header
+            .into_iter()
+            .enumerate()
+            .map(|(index, (line, font_size))| {
+                let y = header_top - index as f32 * HEADER_LINE_HEIGHT;
+                let name = format!("menu_header_{}", index);
+                create_text(world, &font, name, y, HEADER_LINE_HEIGHT, line, UNSELECTED_COLOR, font_size)
+            })
+            .collect();
+
+        Menu {
+            items,
+            selected: 0,
+            header,
+        }
+    }
+
+    pub fn selected(&self) -> T {
+        self.items[self.selected].0
+    }
+
+    // moves the selection with up/down (or w/s) and returns the selected item
+    // when enter is pressed
+    pub fn handle_event(&mut self, world: &mut World, event: &Event) -> Option<T> {
+        if self.items.is_empty() {
+            return None;
+        }
+
+        if is_key_down(event, VirtualKeyCode::Up) || is_key_down(event, VirtualKeyCode::W) {
+            let next = (self.selected + self.items.len() - 1) % self.items.len();
+            self.select(world, next);
+        } else if is_key_down(event, VirtualKeyCode::Down) || is_key_down(event, VirtualKeyCode::S) {
+            let next = (self.selected + 1) % self.items.len();
+            self.select(world, next);
+        } else if is_key_down(event, VirtualKeyCode::Return) {
+            return Some(self.selected());
+        }
+
+        None
+    }
+
+    // replaces the text shown for an item, e.g. to display a setting's value
+    pub fn set_label(&self, world: &mut World, item: T, label: String) {
+        let entity = self.items.iter().find(|(i, _entity)| *i == item).map(|(_item, entity)| *entity);
+
+        if let Some(entity) = entity {
+            if let Some(text) = world.write_storage::<UiText>().get_mut(entity) {
+                text.text = label;
+            }
+        }
+    }
+
+    pub fn delete(&mut self, world: &mut World) {
+        let mut entities: Vec<Entity> = self.items.drain(..).map(|(_item, entity)| entity).collect();
+        entities.append(&mut self.header);
+        let err = "unable to delete menu entities";
+        world.delete_entities(&entities).expect(err);
+    }
+
+    fn select(&mut self, world: &mut World, next: usize) {
+        let mut texts = world.write_storage::<UiText>();
+
+        if let Some(text) = texts.get_mut(self.items[self.selected].1) {
+            text.color = UNSELECTED_COLOR;
+        }
+
+        if let Some(text) = texts.get_mut(self.items[next].1) {
+            text.color = SELECTED_COLOR;
+        }
+
+        self.selected = next;
+    }
+}
+
+// one centered line of menu text, `y` units above the middle of the screen
+#[allow(clippy::too_many_arguments)]
+fn create_text(
+    world: &mut World,
+    font: &Handle<FontAsset>,
+    name: String,
+    y: f32,
+    height: f32,
+    label: String,
+    color: [f32; 4],
+    font_size: f32,
+) -> Entity {
+    let transform = UiTransform::new(name, Anchor::Middle, Anchor::Middle, 0.0, y, 10.0, 800.0, height);
+
+    let mut text = UiText::new(font.clone(), label, color, font_size);
+    text.line_mode = LineMode::Single;
+    text.align = Anchor::Middle;
+
+    world.create_entity().with(transform).with(text).build()
+}
@@ -1,4 +1,18 @@
+pub mod animations;
+pub mod audio;
+pub mod controls;
+#[cfg(feature = "debug-tools")]
+pub mod debug;
 pub mod fade;
+pub mod flowfield;
 pub mod handles;
 pub mod level;
+pub mod menu;
+pub mod particles;
 pub mod playablearea;
+pub mod players;
+pub mod pool;
+pub mod settings;
+pub mod shake;
+pub mod spatialgrid;
+pub mod walls;
@@ -0,0 +1,123 @@
+/// Particle effect settings loaded from `config/particles.ron`, plus the queue
+/// systems use to ask for one-off bursts. `ParticleSystem` is the only thing
+/// that creates particles: it empties the queue each frame and runs every
+/// `Emitter`, taking particle entities from the pool instead of creating them
+/// through `LazyUpdate`.
+use rand::Rng;
+
+use serde::{Deserialize, Serialize};
+
+use std::f32::consts::PI;
+
+// bursts don't have a direction, so their cones are centred on straight up
+pub const BURST_ANGLE: f32 = PI * 0.5;
+
+// bursts spread out in every direction unless the effect says otherwise
+fn full_circle() -> f32 {
+    360.0
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParticleEffect {
+    // each particle picks a value between these two
+    pub lifetime: (f32, f32),
+    pub speed: (f32, f32),
+
+    // width of the cone particles leave in, in degrees
+    #[serde(default = "full_circle")]
+    pub spread: f32,
+
+    // added to each particle's velocity every second
+    #[serde(default)]
+    pub gravity: (f32, f32),
+
+    // (red, green, blue, alpha) at the start and end of a particle's life
+    pub start_color: (f32, f32, f32, f32),
+    pub end_color: (f32, f32, f32, f32),
+
+    // width and height in world units at the start and end of a particle's life
+    pub start_size: f32,
+    pub end_size: f32,
+
+    // how many particles one burst of this effect creates. emitters ignore it
+    #[serde(default)]
+    pub count: usize,
+}
+
+impl ParticleEffect {
+    // a random velocity inside the cone centred on `angle` (in radians)
+    pub fn launch_velocity(&self, angle: f32) -> (f32, f32) {
+        let mut rng = rand::thread_rng();
+        let half_spread = self.spread.to_radians() * 0.5;
+
+        let angle = if half_spread > 0.0 {
+            angle + rng.gen_range(-half_spread, half_spread)
+        } else {
+            angle
+        };
+        let speed = if self.speed.1 > self.speed.0 {
+            rng.gen_range(self.speed.0, self.speed.1)
+        } else {
+            self.speed.0
+        };
+
+        let (sin, cos) = angle.sin_cos();
+        (speed * cos, speed * sin)
+    }
+
+    pub fn pick_lifetime(&self) -> f32 {
+        if self.lifetime.1 > self.lifetime.0 {
+            rand::thread_rng().gen_range(self.lifetime.0, self.lifetime.1)
+        } else {
+            self.lifetime.0
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BurstKind {
+    // an enemy dying
+    Explosion,
+    // a laser hitting an enemy or a wall
+    LaserImpact,
+    // a boss moving on to its next phase
+    BossPhase,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ParticleEffects {
+    pub explosion: ParticleEffect,
+    pub laser_impact: ParticleEffect,
+    pub boss_phase: ParticleEffect,
+    // the most particles alive at once. bursts and emitters skip particles
+    // past this
+    pub max_particles: usize,
+}
+
+impl ParticleEffects {
+    pub fn get(&self, kind: BurstKind) -> &ParticleEffect {
+        match kind {
+            BurstKind::Explosion => &self.explosion,
+            BurstKind::LaserImpact => &self.laser_impact,
+            BurstKind::BossPhase => &self.boss_phase,
+        }
+    }
+}
+
+// bursts requested this frame, with their x and y coordinates
+#[derive(Default)]
+pub struct ParticleBursts {
+    requests: Vec<(BurstKind, f32, f32)>,
+}
+
+impl ParticleBursts {
+    pub fn request(&mut self, kind: BurstKind, x: f32, y: f32) {
+        self.requests.push((kind, x, y));
+    }
+
+    pub fn drain(&mut self) -> Vec<(BurstKind, f32, f32)> {
+        std::mem::take(&mut self.requests)
+    }
+}
@@ -2,8 +2,17 @@
 /// it easy to restrict movement to a specified playable area. This
 /// is intended for use by `gameplay.rs` and should be based on the
 /// screen dimensions.
+/// It's the outer wall of the arena, while walls inside the arena come
+/// from the level layout (see `resources::walls`).
 use amethyst::ecs::{storage::DenseVecStorage, Component};
 
+// how far outside the playable area enemies can go before they're culled.
+// levels spawn enemies well outside the arena, so this needs to be large
+pub const MOVER_CULL_MARGIN: f32 = 1250.0;
+
+// lasers and projectiles only ever fly away once they leave, so they're culled sooner
+pub const SHOT_CULL_MARGIN: f32 = 650.0;
+
 pub enum ClampDimension {
     ClampX,
     ClampY,
@@ -37,6 +46,26 @@ impl PlayableArea {
         }
     }
 
+    // returns (min_x, max_x, min_y, max_y)
+    pub fn bounds(&self) -> (f32, f32, f32, f32) {
+        (self.min_x, self.max_x, self.min_y, self.max_y)
+    }
+
+    pub fn center(&self) -> (f32, f32) {
+        ((self.min_x + self.max_x) * 0.5, (self.min_y + self.max_y) * 0.5)
+    }
+
+    // the bounds grown by `margin` on every side, for culling things that
+    // have left the arena
+    pub fn bounds_with_margin(&self, margin: f32) -> (f32, f32, f32, f32) {
+        (self.min_x - margin, self.max_x + margin, self.min_y - margin, self.max_y + margin)
+    }
+
+    pub fn contains(&self, x: f32, y: f32, margin: f32) -> bool {
+        let (min_x, max_x, min_y, max_y) = self.bounds_with_margin(margin);
+        x >= min_x && x <= max_x && y >= min_y && y <= max_y
+    }
+
     pub fn clamp_x(&self, n: f32) -> f32 {
         self.clamp(n, ClampDimension::ClampX)
     }
@@ -0,0 +1,127 @@
+/// Lives and score for each player, indexed by `PlayerIndex`. This resource
+/// is created fresh when a new game starts and otherwise stays in the world
+/// between levels, so lives and score carry over. Players that die are
+/// respawned at their level spawn point after a short delay, as long as they
+/// have lives left. How many lives everyone starts with depends on the
+/// difficulty picked in the options menu.
+pub const STARTING_LIVES: u32 = 3;
+
+// seconds between dying and respawning
+pub const RESPAWN_DELAY: f32 = 2.0;
+
+#[derive(Clone, Debug)]
+pub struct PlayerRecord {
+    // lives left, including the current one
+    pub lives: u32,
+    pub score: u32,
+    alive: bool,
+    respawn_in: f32,
+}
+
+impl PlayerRecord {
+    pub fn new(lives: u32) -> PlayerRecord {
+        PlayerRecord {
+            lives,
+            score: 0,
+            alive: true,
+            respawn_in: 0.0,
+        }
+    }
+
+    pub fn is_out(&self) -> bool {
+        self.lives == 0
+    }
+}
+
+#[derive(Debug)]
+pub struct PlayerStats {
+    // how many players are in this game. `P2` spawn points in the level are
+    // skipped in one player games
+    player_count: usize,
+    starting_lives: u32,
+    players: Vec<PlayerRecord>,
+}
+
+impl Default for PlayerStats {
+    fn default() -> Self {
+        PlayerStats::new(1, STARTING_LIVES)
+    }
+}
+
+impl PlayerStats {
+    pub fn new(player_count: usize, starting_lives: u32) -> PlayerStats {
+        PlayerStats {
+            player_count,
+            starting_lives,
+            players: Vec::new(),
+        }
+    }
+
+    pub fn is_playing(&self, index: usize) -> bool {
+        index < self.player_count
+    }
+
+    // makes sure there's a record for the player, e.g. when a level spawns
+    // player two for the first time
+    pub fn join(&mut self, index: usize) -> &mut PlayerRecord {
+        if self.players.len() <= index {
+            let lives = self.starting_lives;
+            self.players.resize_with(index + 1, || PlayerRecord::new(lives));
+        }
+        &mut self.players[index]
+    }
+
+    pub fn get(&self, index: usize) -> Option<&PlayerRecord> {
+        self.players.get(index)
+    }
+
+    pub fn records(&self) -> &[PlayerRecord] {
+        &self.players
+    }
+
+    pub fn add_score(&mut self, index: usize, points: u32) {
+        self.join(index).score += points;
+    }
+
+    // records a death. a player can be hit by more than one thing in the
+    // same frame, so this only counts once until they respawn
+    pub fn kill(&mut self, index: usize) {
+        let record = self.join(index);
+        if record.alive {
+            record.alive = false;
+            record.lives = record.lives.saturating_sub(1);
+            record.respawn_in = RESPAWN_DELAY;
+        }
+    }
+
+    // called when a level spawns the player, since a player that died right
+    // as the last level ended gets a fresh start in the next one
+    pub fn spawned(&mut self, index: usize) {
+        let record = self.join(index);
+        record.alive = true;
+        record.respawn_in = 0.0;
+    }
+
+    // ticks the respawn timers and returns the players that should respawn now
+    pub fn tick_respawns(&mut self, time: f32) -> Vec<usize> {
+        let mut ready = Vec::new();
+
+        for (index, record) in self.players.iter_mut().enumerate() {
+            if record.alive || record.is_out() {
+                continue;
+            }
+
+            record.respawn_in -= time;
+            if record.respawn_in <= 0.0 {
+                ready.push(index);
+            }
+        }
+
+        ready
+    }
+
+    // the game is over once every player that has joined is out of lives
+    pub fn all_out(&self) -> bool {
+        !self.players.is_empty() && self.players.iter().all(PlayerRecord::is_out)
+    }
+}
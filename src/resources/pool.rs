@@ -0,0 +1,219 @@
+/// Free lists of entities that are hidden and waiting to be reused, so short
+/// lived things don't create and delete entities (and churn storages) all the
+/// time. `T` is the component that marks what the pool holds, which gives each
+/// kind of pooled entity its own resource. Pooled entities keep their
+/// `CleanupTag`, so a fresh pool is inserted whenever `GameplayState` starts.
+/// Pool sizes are loaded from `config/pools.ron`.
+use amethyst::{
+    core::{Hidden, Transform},
+    ecs::{Component, Entities, Entity, WriteStorage},
+    renderer::SpriteRender,
+};
+
+use serde::{Deserialize, Serialize};
+
+use std::marker::PhantomData;
+
+use crate::components::{animation::Animation, cleanup::CleanupTag};
+
+// used when a pool is created without an explicit cap
+const DEFAULT_CAP: usize = 512;
+
+// the most entities each pool keeps. shots fired while their pool is at its
+// cap are skipped
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolConfig {
+    pub lasers: usize,
+    pub projectiles: usize,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        PoolConfig {
+            lasers: 256,
+            projectiles: 1024,
+        }
+    }
+}
+
+pub struct Pool<T> {
+    free: Vec<Entity>,
+    // how many entities this pool has created, in use or not
+    size: usize,
+    cap: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T> Default for Pool<T> {
+    fn default() -> Self {
+        Pool::new(DEFAULT_CAP)
+    }
+}
+
+impl<T> Pool<T> {
+    pub fn new(cap: usize) -> Self {
+        Pool {
+            free: Vec::new(),
+            size: 0,
+            cap,
+            marker: PhantomData,
+        }
+    }
+
+    // an entity to use, and whether it's a reused one that already has its
+    // components. None means every entity up to the cap is in use
+    pub fn acquire(&mut self, entities: &Entities) -> Option<(Entity, bool)> {
+        while let Some(entity) = self.free.pop() {
+            // something else may have deleted it while it was waiting
+            if entities.is_alive(entity) {
+                return Some((entity, true));
+            }
+            self.size -= 1;
+        }
+
+        if self.size < self.cap {
+            self.size += 1;
+            Some((entities.create(), false))
+        } else {
+            None
+        }
+    }
+
+    // hands an entity back once the caller has hidden and deactivated it
+    pub fn release(&mut self, entity: Entity) {
+        self.free.push(entity);
+    }
+}
+
+impl<T: Component> Pool<T> {
+    // takes an entity out of play instead of deleting it. removing its `T` is
+    // what stops systems from treating it as live, and hiding it stops it from
+    // rendering. entities that are already out of play are left alone, so two
+    // systems can't hand back the same one
+    pub fn recycle(&mut self, entity: Entity, markers: &mut WriteStorage<T>, hiddens: &mut WriteStorage<Hidden>) {
+        if markers.remove(entity).is_some() {
+            hiddens.insert(entity, Hidden).expect("unable to hide pooled entity");
+            self.release(entity);
+        }
+    }
+}
+
+// the storages every pooled shot (lasers and projectiles) uses besides its own
+// components, borrowed by the systems that fire them
+pub struct ShotStorages<'a, 's> {
+    pub entities: &'a Entities<'s>,
+    pub transforms: &'a mut WriteStorage<'s, Transform>,
+    pub sprites: &'a mut WriteStorage<'s, SpriteRender>,
+    pub hiddens: &'a mut WriteStorage<'s, Hidden>,
+    pub cleanup_tags: &'a mut WriteStorage<'s, CleanupTag>,
+    pub animations: &'a mut WriteStorage<'s, Animation>,
+}
+
+impl<'a, 's> ShotStorages<'a, 's> {
+    // an entity from `pool` placed at `transform` and ready to show, and
+    // whether it was reused, or None when the pool is at its cap. reused
+    // entities keep the components they had last time, so anything that never
+    // changes (like the sprite and animation) only has to be added to new ones
+    pub fn acquire<T>(
+        &mut self,
+        pool: &mut Pool<T>,
+        transform: Transform,
+        sprite: &SpriteRender,
+        animation: Option<&Animation>,
+    ) -> Option<(Entity, bool)> {
+        let (entity, reused) = pool.acquire(self.entities)?;
+
+        if reused {
+            self.hiddens.remove(entity);
+        } else {
+            self.sprites.insert(entity, sprite.clone()).expect("unable to create shot");
+            self.cleanup_tags.insert(entity, CleanupTag {}).expect("unable to create shot");
+
+            if let Some(animation) = animation {
+                self.animations.insert(entity, animation.clone()).expect("unable to create shot");
+            }
+        }
+
+        match self.transforms.get_mut(entity) {
+            Some(current) => *current = transform,
+            None => {
+                self.transforms.insert(entity, transform).expect("unable to create shot");
+            },
+        }
+
+        Some((entity, reused))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use amethyst::ecs::{Builder, NullStorage, World, WorldExt};
+
+    #[derive(Default)]
+    struct Shot;
+
+    impl Component for Shot {
+        type Storage = NullStorage<Self>;
+    }
+
+    #[test]
+    fn acquire_stops_at_the_cap() {
+        let world = World::new();
+        let mut pool = Pool::<Shot>::new(2);
+
+        assert_eq!(pool.acquire(&world.entities()).map(|(_, reused)| reused), Some(false));
+        assert_eq!(pool.acquire(&world.entities()).map(|(_, reused)| reused), Some(false));
+        assert!(pool.acquire(&world.entities()).is_none());
+    }
+
+    #[test]
+    fn released_entities_are_reused() {
+        let world = World::new();
+        let mut pool = Pool::<Shot>::new(1);
+
+        let (entity, _) = pool.acquire(&world.entities()).unwrap();
+        pool.release(entity);
+
+        assert_eq!(pool.acquire(&world.entities()), Some((entity, true)));
+        assert!(pool.acquire(&world.entities()).is_none());
+    }
+
+    #[test]
+    fn deleted_entities_free_their_place() {
+        let mut world = World::new();
+        let mut pool = Pool::<Shot>::new(1);
+
+        let (entity, _) = pool.acquire(&world.entities()).unwrap();
+        world.maintain();
+        pool.release(entity);
+        world.delete_entity(entity).unwrap();
+
+        let (replacement, reused) = pool.acquire(&world.entities()).unwrap();
+        assert_ne!(replacement, entity);
+        assert!(!reused);
+    }
+
+    #[test]
+    fn recycling_twice_only_releases_once() {
+        let mut world = World::new();
+        world.register::<Shot>();
+        world.register::<Hidden>();
+        let entity = world.create_entity().with(Shot).build();
+        let mut pool = Pool::<Shot>::new(2);
+
+        {
+            let (mut shots, mut hiddens) = world.system_data::<(WriteStorage<Shot>, WriteStorage<Hidden>)>();
+            pool.recycle(entity, &mut shots, &mut hiddens);
+            pool.recycle(entity, &mut shots, &mut hiddens);
+
+            assert!(!shots.contains(entity));
+            assert!(hiddens.contains(entity));
+        }
+
+        assert_eq!(pool.acquire(&world.entities()), Some((entity, true)));
+        assert_eq!(pool.acquire(&world.entities()).map(|(_, reused)| reused), Some(false));
+    }
+}
@@ -0,0 +1,312 @@
+/// Settings the player can change from the options menu. The shipped defaults
+/// come from `config/display_config.ron`, `config/audio.ron` and the values
+/// below, and the options menu saves the player's choices to
+/// `config/user_settings.ron`. `main.rs` merges that file over the defaults at
+/// startup, so deleting it goes back to the shipped settings.
+use amethyst::{
+    audio::AudioSink,
+    core::frame_limiter::{FrameLimiter, FrameRateLimitStrategy},
+    ecs::{storage::MaskedStorage, Join, ReadStorage, WriteStorage},
+    prelude::*,
+    renderer::{palette::Srgba, resources::Tint},
+    window::{DisplayConfig, Window},
+    winit::dpi::LogicalSize,
+};
+
+use serde::{Deserialize, Serialize};
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    components::hazard::{DamageZone, SlowZone, Teleporter},
+    entities::player::{player_tint, PlayerIndex},
+    resources::{audio::AudioConfig, players::STARTING_LIVES},
+};
+
+// the window size used when the display config doesn't set one
+const DEFAULT_RESOLUTION: (u32, u32) = (1920, 1080);
+
+// the frame rate cap, a typical monitor refresh rate. the renderer always
+// presents with fifo (vsync) and has no way to switch that off, so this is a
+// separate limit that keeps the game from running (and burning power) as fast
+// as it can when vsync isn't holding it back
+const FRAME_CAP_FPS: u32 = 60;
+
+// window sizes the options menu cycles through
+pub const RESOLUTIONS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    // lives each player starts a new game with
+    pub fn starting_lives(self) -> u32 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => STARTING_LIVES,
+            Difficulty::Hard => 1,
+        }
+    }
+
+    // the next difficulty in the options menu, wrapping around
+    pub fn next(self) -> Difficulty {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+}
+
+/// The contents of the user settings file. Anything left out keeps its
+/// shipped default.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UserSettings {
+    pub fullscreen: Option<bool>,
+    pub resolution: Option<(u32, u32)>,
+    pub frame_cap: Option<bool>,
+    pub master_volume: Option<f32>,
+    pub music_volume: Option<f32>,
+    pub sfx_volume: Option<f32>,
+    pub screen_shake: Option<bool>,
+    pub colorblind: Option<bool>,
+    pub difficulty: Option<Difficulty>,
+}
+
+/// The settings in effect, kept as a world resource
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub fullscreen: bool,
+    pub resolution: (u32, u32),
+    pub frame_cap: bool,
+    // each goes from 0.0 to 1.0, like in `AudioConfig`
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub screen_shake: bool,
+    // swaps the colors used to tell players and hazards apart for ones that
+    // work with red-green colorblindness
+    pub colorblind: bool,
+    pub difficulty: Difficulty,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings::new(&DisplayConfig::default(), &AudioConfig::default())
+    }
+}
+
+impl Settings {
+    // the shipped settings
+    pub fn new(display_config: &DisplayConfig, audio_config: &AudioConfig) -> Settings {
+        Settings {
+            fullscreen: display_config.fullscreen.is_some(),
+            resolution: display_config.dimensions.unwrap_or(DEFAULT_RESOLUTION),
+            frame_cap: true,
+            master_volume: audio_config.master_volume,
+            music_volume: audio_config.music_volume,
+            sfx_volume: audio_config.sfx_volume,
+            screen_shake: true,
+            colorblind: false,
+            difficulty: Difficulty::Normal,
+        }
+    }
+
+    // where the options menu saves the player's settings
+    pub fn user_settings_path(config_dir: &Path) -> PathBuf {
+        config_dir.join("user_settings.ron")
+    }
+
+    pub fn merge(&mut self, user: &UserSettings) {
+        self.fullscreen = user.fullscreen.unwrap_or(self.fullscreen);
+        self.resolution = user.resolution.unwrap_or(self.resolution);
+        self.frame_cap = user.frame_cap.unwrap_or(self.frame_cap);
+        self.master_volume = user.master_volume.unwrap_or(self.master_volume);
+        self.music_volume = user.music_volume.unwrap_or(self.music_volume);
+        self.sfx_volume = user.sfx_volume.unwrap_or(self.sfx_volume);
+        self.screen_shake = user.screen_shake.unwrap_or(self.screen_shake);
+        self.colorblind = user.colorblind.unwrap_or(self.colorblind);
+        self.difficulty = user.difficulty.unwrap_or(self.difficulty);
+    }
+
+    // everything, for saving to the user settings file
+    pub fn to_user_settings(&self) -> UserSettings {
+        UserSettings {
+            fullscreen: Some(self.fullscreen),
+            resolution: Some(self.resolution),
+            frame_cap: Some(self.frame_cap),
+            master_volume: Some(self.master_volume),
+            music_volume: Some(self.music_volume),
+            sfx_volume: Some(self.sfx_volume),
+            screen_shake: Some(self.screen_shake),
+            colorblind: Some(self.colorblind),
+            difficulty: Some(self.difficulty),
+        }
+    }
+
+    // the window size. fullscreen needs a monitor, which doesn't exist until
+    // the window does, so that's left to `apply_settings`
+    pub fn apply_display(&self, display_config: &mut DisplayConfig) {
+        display_config.dimensions = Some(self.resolution);
+    }
+
+    pub fn apply_audio(&self, audio_config: &mut AudioConfig) {
+        audio_config.master_volume = self.master_volume;
+        audio_config.music_volume = self.music_volume;
+        audio_config.sfx_volume = self.sfx_volume;
+    }
+
+    pub fn frame_limit(&self) -> (FrameRateLimitStrategy, u32) {
+        if self.frame_cap {
+            (
+                FrameRateLimitStrategy::SleepAndYield(Duration::from_millis(2)),
+                FRAME_CAP_FPS,
+            )
+        } else {
+            (FrameRateLimitStrategy::Unlimited, 0)
+        }
+    }
+
+    // hazard tile colors: (electrified floor, slow zone, teleporter)
+    pub fn hazard_colors(&self) -> (Srgba, Srgba, Srgba) {
+        if self.colorblind {
+            (
+                Srgba::new(0.9, 0.6, 0.0, 0.5),
+                Srgba::new(0.35, 0.7, 0.9, 0.5),
+                Srgba::new(0.95, 0.95, 0.95, 0.6),
+            )
+        } else {
+            (
+                Srgba::new(1.0, 0.9, 0.1, 0.5),
+                Srgba::new(0.2, 0.4, 1.0, 0.5),
+                Srgba::new(0.9, 0.2, 0.9, 0.6),
+            )
+        }
+    }
+}
+
+// applies the settings resource to the running game: the window, the frame
+// rate, the music volume and the palette. sound effect volumes are read as
+// they play
+pub fn apply_settings(world: &mut World) {
+    let settings = (*world.read_resource::<Settings>()).clone();
+
+    {
+        let mut audio_config = world.write_resource::<AudioConfig>();
+        settings.apply_audio(&mut audio_config);
+
+        if let Some(mut sink) = world.try_fetch_mut::<AudioSink>() {
+            sink.set_volume(audio_config.music_level());
+        }
+    }
+
+    apply_palette(world, &settings);
+
+    let (strategy, fps) = settings.frame_limit();
+    world.write_resource::<FrameLimiter>().set_rate(strategy, fps);
+
+    if let Some(window) = world.try_fetch::<Window>() {
+        if settings.fullscreen {
+            window.set_fullscreen(Some(window.get_current_monitor()));
+        } else {
+            window.set_fullscreen(None);
+            let (width, height) = settings.resolution;
+            window.set_inner_size(LogicalSize::new(f64::from(width), f64::from(height)));
+        }
+    }
+}
+
+// recolors the players and hazard tiles already in the level, since they're
+// only tinted for the palette when they spawn
+fn apply_palette(world: &mut World, settings: &Settings) {
+    // the gameplay components are registered when the first level starts, and
+    // there's nothing to recolor before then
+    if !world.has_value::<MaskedStorage<PlayerIndex>>() {
+        return;
+    }
+
+    let (damage_color, slow_color, teleporter_color) = settings.hazard_colors();
+
+    #[allow(clippy::type_complexity)]
+    let (player_indexes, damage_zones, slow_zones, teleporters, mut tints) = world.system_data::<(
+        ReadStorage<PlayerIndex>,
+        ReadStorage<DamageZone>,
+        ReadStorage<SlowZone>,
+        ReadStorage<Teleporter>,
+        WriteStorage<Tint>,
+    )>();
+
+    for (player_index, tint) in (&player_indexes, &mut tints).join() {
+        *tint = player_tint(player_index.0, settings.colorblind);
+    }
+
+    for (_damage_zone, tint) in (&damage_zones, &mut tints).join() {
+        tint.0 = damage_color;
+    }
+
+    for (_slow_zone, tint) in (&slow_zones, &mut tints).join() {
+        tint.0 = slow_color;
+    }
+
+    for (_teleporter, tint) in (&teleporters, &mut tints).join() {
+        tint.0 = teleporter_color;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_keeps_defaults_for_missing_settings() {
+        let mut settings = Settings::default();
+        let defaults = settings.clone();
+
+        settings.merge(&UserSettings {
+            colorblind: Some(true),
+            music_volume: Some(0.2),
+            ..UserSettings::default()
+        });
+
+        assert!(settings.colorblind);
+        assert_eq!(settings.music_volume, 0.2);
+        assert_eq!(settings.resolution, defaults.resolution);
+        assert_eq!(settings.difficulty, defaults.difficulty);
+        assert_eq!(settings.frame_cap, defaults.frame_cap);
+    }
+
+    #[test]
+    fn saved_settings_load_back_the_same() {
+        let saved = Settings {
+            fullscreen: true,
+            resolution: (1280, 720),
+            frame_cap: false,
+            sfx_volume: 0.3,
+            difficulty: Difficulty::Hard,
+            ..Settings::default()
+        };
+
+        let contents = ron::ser::to_string(&saved.to_user_settings()).unwrap();
+        let user: UserSettings = ron::de::from_str(&contents).unwrap();
+
+        let mut loaded = Settings::default();
+        loaded.merge(&user);
+        assert_eq!(loaded, saved);
+    }
+
+    #[test]
+    fn user_settings_files_can_leave_things_out() {
+        let user: UserSettings = ron::de::from_str("(difficulty: Some(Easy))").unwrap();
+
+        assert_eq!(user.difficulty, Some(Difficulty::Easy));
+        assert!(user.fullscreen.is_none());
+    }
+}
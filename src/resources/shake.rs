@@ -0,0 +1,44 @@
+/// Camera shake for big moments, like a player dying or a boss changing
+/// phase. Systems add trauma, and `ScreenShakeSystem` turns it into a camera
+/// offset that fades out. Players can turn it off from the options menu.
+use rand::Rng;
+
+// how much trauma each event adds. trauma is capped at 1.0
+pub const PLAYER_DEATH_TRAUMA: f32 = 0.6;
+pub const BOSS_PHASE_TRAUMA: f32 = 0.4;
+
+// the camera offset at full trauma, in world units
+const MAX_OFFSET: f32 = 24.0;
+
+// trauma lost per second
+const RECOVERY: f32 = 1.5;
+
+#[derive(Default)]
+pub struct ScreenShake {
+    trauma: f32,
+}
+
+impl ScreenShake {
+    pub fn add(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.0);
+    }
+
+    pub fn clear(&mut self) {
+        self.trauma = 0.0;
+    }
+
+    // the camera offset for this frame. squaring the trauma makes small hits
+    // subtle and big ones obvious
+    pub fn update(&mut self, time_delta: f32) -> (f32, f32) {
+        if self.trauma <= 0.0 {
+            return (0.0, 0.0);
+        }
+
+        let mut rng = rand::thread_rng();
+        let strength = self.trauma * self.trauma * MAX_OFFSET;
+        let offset = (strength * rng.gen_range(-1.0, 1.0), strength * rng.gen_range(-1.0, 1.0));
+
+        self.trauma = (self.trauma - RECOVERY * time_delta).max(0.0);
+        offset
+    }
+}
@@ -0,0 +1,47 @@
+/// A uniform grid for finding nearby entities without checking every pair.
+/// Positions are bucketed into square cells, and a neighbor query only looks
+/// at the cells overlapping the search radius. The grid stores indexes into
+/// whatever list the caller built it from, so it's cheap to rebuild each frame.
+use std::collections::HashMap;
+
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> SpatialGrid {
+        SpatialGrid {
+            // a zero cell size would put everything at infinity
+            cell_size: cell_size.max(1.0),
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, x: f32, y: f32, index: usize) {
+        let cell = self.cell(x, y);
+        self.cells.entry(cell).or_insert_with(Vec::new).push(index);
+    }
+
+    // returns every index in the cells overlapping the radius. callers still
+    // need to check the actual distance, since cells are square
+    pub fn candidates(&self, x: f32, y: f32, radius: f32) -> Vec<usize> {
+        let (min_x, min_y) = self.cell(x - radius, y - radius);
+        let (max_x, max_y) = self.cell(x + radius, y + radius);
+
+        let mut found = Vec::new();
+        for cell_x in min_x ..= max_x {
+            for cell_y in min_y ..= max_y {
+                if let Some(indexes) = self.cells.get(&(cell_x, cell_y)) {
+                    found.extend_from_slice(indexes);
+                }
+            }
+        }
+
+        found
+    }
+
+    fn cell(&self, x: f32, y: f32) -> (i32, i32) {
+        ((x / self.cell_size).floor() as i32, (y / self.cell_size).floor() as i32)
+    }
+}
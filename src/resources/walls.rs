@@ -0,0 +1,98 @@
+/// The solid level geometry for the current level, built from the `#` cells
+/// in `config/levels.ron`. Walls never move, so their boxes are computed once
+/// per level and indexed with a `SpatialGrid`. `PlayableArea` is still the
+/// outer wall of the arena; these are the obstacles inside it.
+use ncollide2d::bounding_volume::{BoundingVolume, AABB};
+
+use std::collections::HashSet;
+
+use crate::{
+    components::collider::{segment_intersects, Collider},
+    resources::{
+        level::{world_to_cell, EntityType, LevelMetadata, CELL_HEIGHT, CELL_WIDTH},
+        spatialgrid::SpatialGrid,
+    },
+};
+
+// each wall fills one cell of the level grid
+pub const WALL_COLLIDER: Collider = Collider {
+    half_width: CELL_WIDTH * 0.5,
+    half_height: CELL_HEIGHT * 0.5,
+};
+
+// pushing out of one wall can push a mover into the next, so resolving
+// collisions is repeated a few times
+const MAX_PUSHES: usize = 4;
+
+pub struct Walls {
+    boxes: Vec<AABB<f32>>,
+    grid: SpatialGrid,
+    // the level grid cells that are walls, for pathfinding
+    cells: HashSet<(i32, i32)>,
+}
+
+impl Default for Walls {
+    fn default() -> Self {
+        Walls {
+            boxes: Vec::new(),
+            grid: SpatialGrid::new(CELL_WIDTH.max(CELL_HEIGHT)),
+            cells: HashSet::new(),
+        }
+    }
+}
+
+impl Walls {
+    pub fn new(level_metadata: &LevelMetadata) -> Walls {
+        let mut walls = Walls::default();
+
+        for (entity_type, x, y) in level_metadata.get_layout() {
+            if *entity_type == EntityType::Wall {
+                walls.grid.insert(*x, *y, walls.boxes.len());
+                walls.boxes.push(WALL_COLLIDER.aabb_from_coordinates(*x, *y));
+                walls.cells.insert(world_to_cell(*x, *y));
+            }
+        }
+
+        walls
+    }
+
+    // walls that might touch a collider at (x, y)
+    fn nearby(&self, collider: &Collider, x: f32, y: f32) -> impl Iterator<Item = &AABB<f32>> + '_ {
+        let radius = collider.half_width.max(collider.half_height) + CELL_WIDTH.max(CELL_HEIGHT);
+        self.grid.candidates(x, y, radius).into_iter().map(move |index| &self.boxes[index])
+    }
+
+    pub fn is_wall_cell(&self, cell: (i32, i32)) -> bool {
+        self.cells.contains(&cell)
+    }
+
+    pub fn blocks(&self, collider: &Collider, x: f32, y: f32) -> bool {
+        let aabb = collider.aabb_from_coordinates(x, y);
+        self.nearby(collider, x, y).any(|wall| wall.intersects(&aabb))
+    }
+
+    // moves a collider at (x, y) out of any walls it overlaps and returns the
+    // corrected position
+    pub fn push_out(&self, collider: &Collider, x: f32, y: f32) -> (f32, f32) {
+        let (mut x, mut y) = (x, y);
+
+        for _ in 0 .. MAX_PUSHES {
+            let push = self.nearby(collider, x, y).find_map(|wall| collider.push_out(x, y, wall));
+
+            match push {
+                Some((push_x, push_y)) => {
+                    x += push_x;
+                    y += push_y;
+                },
+                None => break,
+            }
+        }
+
+        (x, y)
+    }
+
+    // whether anything blocks the straight line between two points
+    pub fn line_of_sight(&self, x1: f32, y1: f32, x2: f32, y2: f32) -> bool {
+        !self.boxes.iter().any(|wall| segment_intersects(wall, x1, y1, x2, y2))
+    }
+}
@@ -0,0 +1,74 @@
+/// Shown once every player is out of lives. It lists each player's final
+/// score and lets them go back to the main menu, which starts a new game
+/// with fresh lives.
+use amethyst::{
+    input::{is_close_requested, is_key_down, VirtualKeyCode},
+    prelude::*,
+};
+
+use crate::{
+    resources::{menu::Menu, players::PlayerStats},
+    states::menu::MainMenuState,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GameOverOption {
+    MainMenu,
+    Quit,
+}
+
+#[derive(Default)]
+pub struct GameOverState {
+    menu: Option<Menu<GameOverOption>>,
+}
+
+impl SimpleState for GameOverState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+
+        let scores: Vec<String> = world
+            .read_resource::<PlayerStats>()
+            .records()
+            .iter()
+            .enumerate()
+            .map(|(index, record)| format!("P{}  Score: {}", index + 1, record.score))
+            .collect();
+
+        // the title and scores go above the options
+        let mut header = vec![("Game Over".to_string(), 60.0)];
+        header.extend(scores.into_iter().map(|score| (score, 36.0)));
+
+        self.menu = Some(Menu::with_header(world, header, vec![
+            (GameOverOption::MainMenu, "Main Menu".to_string()),
+            (GameOverOption::Quit, "Quit".to_string()),
+        ]));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(mut menu) = self.menu.take() {
+            menu.delete(data.world);
+        }
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(&event) {
+                return Trans::Quit;
+            }
+
+            if is_key_down(&event, VirtualKeyCode::Escape) {
+                return Trans::Switch(Box::new(MainMenuState::default()));
+            }
+
+            let selection = self.menu.as_mut().and_then(|menu| menu.handle_event(data.world, &event));
+
+            match selection {
+                Some(GameOverOption::MainMenu) => return Trans::Switch(Box::new(MainMenuState::default())),
+                Some(GameOverOption::Quit) => return Trans::Quit,
+                None => {},
+            }
+        }
+
+        Trans::None
+    }
+}
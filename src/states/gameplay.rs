@@ -5,15 +5,17 @@
 ///   2) setup the dispatcher so the systems here won't run in other states
 ///   3) act as the game's state manager (deciding when to switch states)
 use amethyst::{
-    assets::{Handle, PrefabLoader, ProgressCounter, RonFormat},
+    assets::{AssetStorage, Handle, Loader, PrefabLoader, ProgressCounter, RonFormat},
     core::math::{Translation3, UnitQuaternion, Vector3},
-    core::{transform::Transform, ArcThreadPool},
-    ecs::prelude::{Dispatcher, DispatcherBuilder, Join},
+    core::{timing::Time, transform::Transform, ArcThreadPool},
+    ecs::prelude::{Dispatcher, DispatcherBuilder, Entity, EntityBuilder, Join},
     ecs::world::EntitiesRes,
     input::{is_close_requested, is_key_down, VirtualKeyCode},
     prelude::*,
-    renderer::{Camera, SpriteRender, SpriteSheet},
+    renderer::{palette::Srgba, resources::Tint, Camera, SpriteRender, SpriteSheet, Transparent},
+    ui::{get_default_font, Anchor, FontAsset, LineMode, UiImage, UiText, UiTransform},
     window::ScreenDimensions,
+    winit::{Event, WindowEvent},
 };
 
 use derive_new::new;
@@ -21,21 +23,51 @@ use derive_new::new;
 use crate::entities::{
     enemy::{Enemy, EnemyPrefab},
     laser::Laser,
-    player::{Player, PlayerPrefab},
+    player::{player_tint, Player, PlayerIndex, PlayerPrefab},
 };
 
 use crate::{
-    components::{cleanup::CleanupTag, collider::Collider, launcher::Launcher, movement::Movement},
+    components::{
+        ai::Ai,
+        animation::Animation,
+        boss::Boss,
+        cleanup::CleanupTag,
+        collider::Collider,
+        cooldown::Cooldown,
+        effects::HitFlash,
+        hazard::{DamageZone, SlowZone, Teleporter, HAZARD_COLLIDER},
+        hud::{BossHealthBar, PlayerHud},
+        launcher::{Launcher, Projectile},
+        movement::Movement,
+        particles::{Emitter, Particle},
+        spawnpoint::SpawnPoint,
+        steering::Steering,
+        targeting::Targeting,
+    },
     resources::{
+        audio::{self, SoundHandles},
+        flowfield::FlowFields,
         handles,
         handles::GameplayHandles,
-        level::{EntityType, LevelMetadata, Levels},
+        level::{EntityType, LevelMetadata, LevelProgress, Levels},
+        particles::ParticleEffects,
         playablearea::PlayableArea,
+        players::PlayerStats,
+        pool::{Pool, PoolConfig},
+        settings::Settings,
+        shake::ScreenShake,
+        walls::{Walls, WALL_COLLIDER},
     },
-    states::{paused::PausedState, transition::TransitionState},
+    states::{gameover::GameOverState, paused::PausedState, transition::TransitionState},
     systems,
 };
 
+#[cfg(feature = "debug-tools")]
+use amethyst::input::{InputHandler, StringBindings};
+
+#[cfg(feature = "debug-tools")]
+use crate::resources::{controls::screen_to_world, debug::DebugTools};
+
 use log::info;
 
 /// Collects our state-specific dispatcher, progress counter for asset
@@ -50,6 +82,19 @@ pub struct GameplayState<'a, 'b> {
     #[new(default)]
     pub level_is_loaded: bool,
 
+    // the level currently being played, kept around so it can be restarted
+    #[new(default)]
+    pub current_level: Option<LevelMetadata>,
+
+    // set when the level is complete. the camera and background are left
+    // for `TransitionState` to fade over, and it cleans them up instead
+    #[new(default)]
+    pub level_complete: bool,
+
+    // the camera and background entities
+    #[new(default)]
+    pub scene: Vec<Entity>,
+
     #[new(default)]
     pub handles: Option<GameplayHandles>,
 
@@ -68,14 +113,50 @@ impl<'a, 'b> SimpleState for GameplayState<'a, 'b> {
         // creates a dispatcher to collect systems specific to this state
         let mut dispatcher_builder = DispatcherBuilder::new();
 
-        dispatcher_builder.add(systems::PlayerSystem, "player_system", &[]);
+        dispatcher_builder.add(systems::CooldownSystem, "cooldown_system", &[]);
+        dispatcher_builder.add(systems::HazardSystem, "hazard_system", &[]);
+        dispatcher_builder.add(systems::PlayerSystem, "player_system", &["hazard_system"]);
         dispatcher_builder.add(systems::LaserSystem, "laser_system", &[]);
         dispatcher_builder.add(systems::CollisionSystem, "collision_system", &[]);
         dispatcher_builder.add(systems::AttackedSystem, "attacked_system", &[]);
         dispatcher_builder.add(systems::ProjectileHitSystem, "projectile_hit_system", &[]);
-        dispatcher_builder.add(systems::MovementTrackingSystem, "movement_tracking_system", &[]);
-        dispatcher_builder.add(systems::TransformUpdateSystem, "transform_update_system", &[]);
+        dispatcher_builder.add(systems::AiSystem, "ai_system", &[]);
+        dispatcher_builder.add(systems::MovementTrackingSystem, "movement_tracking_system", &["ai_system"]);
+        dispatcher_builder.add(systems::SteeringSystem, "steering_system", &["movement_tracking_system"]);
+        dispatcher_builder.add(systems::TransformUpdateSystem, "transform_update_system", &["steering_system"]);
+        dispatcher_builder.add(
+            systems::WallCollisionSystem,
+            "wall_collision_system",
+            &["player_system", "laser_system", "transform_update_system"],
+        );
         dispatcher_builder.add(systems::ProjectilesSystem, "projectiles_system", &[]);
+        dispatcher_builder.add(systems::BossSystem, "boss_system", &[]);
+        dispatcher_builder.add(systems::BossHealthBarSystem, "boss_health_bar_system", &[]);
+        dispatcher_builder.add(systems::PlayerHudSystem, "player_hud_system", &[]);
+        dispatcher_builder.add(
+            systems::AnimationSystem,
+            "animation_system",
+            &["boss_system", "transform_update_system", "collision_system"],
+        );
+        dispatcher_builder.add(systems::EffectsSystem, "effects_system", &["collision_system"]);
+        dispatcher_builder.add(
+            systems::ParticleSystem,
+            "particle_system",
+            &["collision_system", "wall_collision_system", "boss_system"],
+        );
+        dispatcher_builder.add(
+            systems::AnimationCleanupSystem::default(),
+            "animation_cleanup_system",
+            &["animation_system"],
+        );
+        dispatcher_builder.add(
+            systems::ScreenShakeSystem,
+            "screen_shake_system",
+            &["attacked_system", "projectile_hit_system", "hazard_system", "boss_system"],
+        );
+
+        #[cfg(feature = "debug-tools")]
+        dispatcher_builder.add(systems::DebugOverlaySystem, "debug_overlay_system", &[]);
 
         // builds and sets up the dispatcher
         let mut dispatcher = dispatcher_builder
@@ -92,7 +173,7 @@ impl<'a, 'b> SimpleState for GameplayState<'a, 'b> {
         info!("computed dimensions are: {:?}", &dimensions);
 
         // Place the camera
-        init_camera(world, &dimensions);
+        self.scene = vec![init_camera(world, &dimensions)];
 
         // easier to load the prefab handles here and then pass them to
         let enemy_prefab_handle = world.exec(|loader: PrefabLoader<'_, EnemyPrefab>| {
@@ -103,6 +184,10 @@ impl<'a, 'b> SimpleState for GameplayState<'a, 'b> {
             loader.load("prefabs/flying_enemy.ron", RonFormat, &mut self.progress_counter)
         });
 
+        let hunter_enemy_prefab_handle = world.exec(|loader: PrefabLoader<'_, EnemyPrefab>| {
+            loader.load("prefabs/hunter_enemy.ron", RonFormat, &mut self.progress_counter)
+        });
+
         let player_prefab_handle = world.exec(|loader: PrefabLoader<'_, PlayerPrefab>| {
             loader.load("prefabs/player.ron", RonFormat, &mut self.progress_counter)
         });
@@ -117,27 +202,62 @@ impl<'a, 'b> SimpleState for GameplayState<'a, 'b> {
             &mut self.progress_counter,
             enemy_prefab_handle,
             flying_enemy_prefab_handle,
+            hunter_enemy_prefab_handle,
             player_prefab_handle,
             boss_prefab_handle,
         );
+        // systems that spawn effects read their sprites from the world's copy
+        world.insert(gameplay_handles.clone());
+        // sound effects are the same for every level, so they're only loaded once
+        if !world.has_value::<SoundHandles>() {
+            let sound_handles = audio::get_sound_handles(world, &mut self.progress_counter);
+            world.insert(sound_handles);
+        }
+
+        // particles and shots from the last level were cleaned up with
+        // everything else, so each level starts with empty pools
+        let max_particles = world.read_resource::<ParticleEffects>().max_particles;
+        world.insert(Pool::<Particle>::new(max_particles));
+        let pool_config = (*world.read_resource::<PoolConfig>()).clone();
+        world.insert(Pool::<Laser>::new(pool_config.lasers));
+        world.insert(Pool::<Projectile>::new(pool_config.projectiles));
+        world.insert(ScreenShake::default());
+        world.insert(LevelProgress::default());
         self.handles = Some(gameplay_handles);
 
         // render the background
-        init_background(
+        let background = init_background(
             world,
             &dimensions,
             self.handles.clone().unwrap().background_sprite_handle,
         );
+        self.scene.push(background);
 
         // register our entities and resources before inserting them or
         // having them created as part of `init_level` in `update`
         world.register::<CleanupTag>();
         world.register::<Player>();
+        world.register::<PlayerIndex>();
         world.register::<Laser>();
         world.register::<Enemy>();
         world.register::<Collider>();
         world.register::<Movement>();
         world.register::<Launcher>();
+        world.register::<Cooldown>();
+        world.register::<Boss>();
+        world.register::<Ai>();
+        world.register::<Animation>();
+        world.register::<HitFlash>();
+        world.register::<Particle>();
+        world.register::<Emitter>();
+        world.register::<SpawnPoint>();
+        world.register::<DamageZone>();
+        world.register::<SlowZone>();
+        world.register::<Teleporter>();
+        world.register::<Steering>();
+        world.register::<Targeting>();
+        world.register::<BossHealthBar>();
+        world.register::<PlayerHud>();
         world.register::<PlayableArea>();
 
         // setup the playable area. depending on how many backgrounds we have these
@@ -157,6 +277,22 @@ impl<'a, 'b> SimpleState for GameplayState<'a, 'b> {
         let handles = self.handles.clone().expect("failure accessing GameplayHandles struct");
 
         if let Some(next_level_metadata) = next_level {
+            let has_boss = next_level_metadata
+                .get_layout()
+                .iter()
+                .any(|(entity_type, _x, _y)| *entity_type == EntityType::Boss);
+
+            if has_boss {
+                init_boss_health_bar(world);
+            }
+
+            init_player_huds(world, &next_level_metadata);
+            world.insert(Walls::new(&next_level_metadata));
+            world.insert(FlowFields::default());
+
+            audio::play_music(world, next_level_metadata.get_music());
+
+            self.current_level = Some(next_level_metadata.clone());
             init_level(world, next_level_metadata, handles);
         }
     }
@@ -170,14 +306,29 @@ impl<'a, 'b> SimpleState for GameplayState<'a, 'b> {
             }
         }
 
+        self.respawn_players(data.world);
+
+        // with more than one player, the game keeps going as long as anyone has lives left
+        if data.world.read_resource::<PlayerStats>().all_out() {
+            return Trans::Switch(Box::new(GameOverState::default()));
+        }
+
         // this removes the need to track a count of enemies and have multiple
         // systems read and write to that resource
-        let total = {
+        let (total, bosses) = {
             let entities = data.world.read_resource::<EntitiesRes>();
             let enemies = data.world.read_storage::<Enemy>();
-            (&entities, &enemies).join().count()
+            let bosses = data.world.read_storage::<Boss>();
+            (
+                (&entities, &enemies).join().count(),
+                (&entities, &bosses).join().count(),
+            )
         };
 
+        // defeating the boss wins the level, even if other enemies are left. the
+        // level ends once the boss entity is gone
+        let boss_defeated = data.world.read_resource::<LevelProgress>().boss_defeated && bosses == 0;
+
         // still hacky. checks that we have at least 1 enemy to decide
         // that the level is loaded. this is because the levels inserted
         // into the world may not be loaded the first time this update is called
@@ -186,13 +337,10 @@ impl<'a, 'b> SimpleState for GameplayState<'a, 'b> {
         }
 
         // this branch decides whether or not to switch state. if a level is
-        // loaded and all enemies are defeated, it's time to transition, otherwise
-        // keep going
-        if total == 0 && self.level_is_loaded {
-            Trans::Switch(Box::new(TransitionState::new(
-                self.handles.clone().unwrap().overlay_sprite_handle,
-                self.levels.clone(),
-            )))
+        // loaded and all enemies (or the boss) are defeated, it's time to transition,
+        // otherwise keep going
+        if (total == 0 && self.level_is_loaded) || boss_defeated {
+            self.transition()
         }
         // otherwise, nothing to see here folks!
         else {
@@ -201,24 +349,20 @@ impl<'a, 'b> SimpleState for GameplayState<'a, 'b> {
     }
 
     // handles pausing (toggling the `p` key) and closing (window close or pressing escape)
-    fn handle_event(&mut self, _data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
+    fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
         if let StateEvent::Window(event) = &event {
             // Check if the window should be closed
             if is_close_requested(&event) || is_key_down(&event, VirtualKeyCode::Escape) {
                 return Trans::Quit;
             }
 
-            if is_key_down(&event, VirtualKeyCode::P) {
-                return Trans::Push(Box::new(PausedState));
+            // pausing on focus loss keeps the player from dying off-screen if they alt-tab.
+            // `PausedState` only resumes on explicit input, not when focus returns
+            if is_key_down(&event, VirtualKeyCode::P) || lost_focus(&event) {
+                return Trans::Push(Box::new(self.paused_state()));
             }
 
-            // when should this actually run?
-            if is_key_down(&event, VirtualKeyCode::Space) {
-                return Trans::Switch(Box::new(TransitionState::new(
-                    self.handles.clone().unwrap().overlay_sprite_handle,
-                    self.levels.clone(),
-                )));
-            }
+            return self.handle_debug_event(data.world, event);
         }
 
         // no state changes required
@@ -233,13 +377,143 @@ impl<'a, 'b> SimpleState for GameplayState<'a, 'b> {
         let cleanup_tags = data.world.read_storage::<CleanupTag>();
 
         for (entity, _tag) in (&entities, &cleanup_tags).join() {
+            if self.level_complete && self.scene.contains(&entity) {
+                continue;
+            }
+
             let err = format!("unable to delete entity: {:?}", entity);
             entities.delete(entity).expect(&err);
         }
     }
 }
 
-fn init_camera(world: &mut World, dimensions: &ScreenDimensions) {
+impl<'a, 'b> GameplayState<'a, 'b> {
+    // moves on to the transition into the next level
+    fn transition(&mut self) -> SimpleTrans {
+        self.level_complete = true;
+
+        Trans::Switch(Box::new(TransitionState::new(
+            self.handles.clone().unwrap().overlay_sprite_handle,
+            self.levels.clone(),
+        )))
+    }
+
+    // players that died with lives left come back at their spawn point in the
+    // current level once their respawn delay is up
+    fn respawn_players(&self, world: &mut World) {
+        let ready = {
+            let time = world.read_resource::<Time>();
+            world.write_resource::<PlayerStats>().tick_respawns(time.delta_seconds())
+        };
+
+        if let (Some(level), Some(handles)) = (self.current_level.as_ref(), self.handles.as_ref()) {
+            for index in ready {
+                // players without a spawn point of their own come back at player
+                // one's, or in the middle of the arena if there isn't one either
+                let (x, y) = level
+                    .get_layout()
+                    .iter()
+                    .find(|(entity_type, _x, _y)| *entity_type == EntityType::Player(index))
+                    .or_else(|| {
+                        level
+                            .get_layout()
+                            .iter()
+                            .find(|(entity_type, _x, _y)| *entity_type == EntityType::Player(0))
+                    })
+                    .map(|(_entity_type, x, y)| (*x, *y))
+                    .unwrap_or_else(|| world.read_resource::<PlayableArea>().center());
+
+                info!("respawning player {}", index + 1);
+                spawn_level_entity(world, handles, &EntityType::Player(index), x, y);
+            }
+        }
+    }
+
+    // the pause menu can restart the current level, so it gets the remaining
+    // levels with the current one pushed back on top
+    fn paused_state(&self) -> PausedState {
+        let mut restart_levels = self.levels.clone();
+        if let Some(current_level) = self.current_level.clone() {
+            restart_levels.push(current_level);
+        }
+
+        PausedState::new(self.handles.clone().unwrap().overlay_sprite_handle, restart_levels)
+    }
+
+    // developer shortcuts, only compiled in with `--features debug-tools`:
+    //   Space: skip the current level
+    //   G: toggle god mode
+    //   K: kill all enemies
+    //   E: spawn a square enemy at the cursor
+    //   T: toggle slow motion
+    //   C: toggle the collider/bounds overlay
+    #[cfg(feature = "debug-tools")]
+    fn handle_debug_event(&mut self, world: &mut World, event: &Event) -> SimpleTrans {
+        if is_key_down(event, VirtualKeyCode::Space) {
+            return self.transition();
+        }
+
+        if is_key_down(event, VirtualKeyCode::G) {
+            let enabled = world
+                .entry::<DebugTools>()
+                .or_insert_with(DebugTools::default)
+                .toggle_god_mode();
+            info!("god mode enabled: {}", enabled);
+        }
+
+        if is_key_down(event, VirtualKeyCode::K) {
+            kill_all_enemies(world);
+        }
+
+        if is_key_down(event, VirtualKeyCode::E) {
+            if let (Some((x, y)), Some(handles)) = (cursor_world_position(world), self.handles.clone()) {
+                spawn_level_entity(world, &handles, &EntityType::SquareEnemy, x, y);
+            }
+        }
+
+        if is_key_down(event, VirtualKeyCode::T) {
+            let time_scale = world
+                .entry::<DebugTools>()
+                .or_insert_with(DebugTools::default)
+                .toggle_slow_motion();
+            world.write_resource::<Time>().set_time_scale(time_scale);
+        }
+
+        if is_key_down(event, VirtualKeyCode::C) {
+            let enabled = world
+                .entry::<DebugTools>()
+                .or_insert_with(DebugTools::default)
+                .toggle_overlay();
+            info!("debug overlay enabled: {}", enabled);
+        }
+
+        Trans::None
+    }
+
+    // shipping builds don't have any developer shortcuts
+    #[cfg(not(feature = "debug-tools"))]
+    fn handle_debug_event(&mut self, _world: &mut World, _event: &Event) -> SimpleTrans {
+        Trans::None
+    }
+}
+
+// true when the window loses focus or is minimized. some platforms don't send
+// a focus event when minimizing, but do report a resize to zero
+fn lost_focus(event: &Event) -> bool {
+    match event {
+        Event::WindowEvent {
+            event: WindowEvent::Focused(false),
+            ..
+        } => true,
+        Event::WindowEvent {
+            event: WindowEvent::Resized(size),
+            ..
+        } => size.width <= 0.0 || size.height <= 0.0,
+        _ => false,
+    }
+}
+
+fn init_camera(world: &mut World, dimensions: &ScreenDimensions) -> Entity {
     // Center the camera in the middle of the screen, and let it cover
     // the entire screen
     let mut transform = Transform::default();
@@ -252,12 +526,17 @@ fn init_camera(world: &mut World, dimensions: &ScreenDimensions) {
         .create_entity()
         .with(Camera::standard_2d(1920.0, 1080.0))
         .with(transform)
-        .build();
+        .with(CleanupTag {})
+        .build()
 }
 
 // render the background, giving it a low z value so it renders under
 // everything else
-fn init_background(world: &mut World, dimensions: &ScreenDimensions, bg_sprite_sheet_handle: Handle<SpriteSheet>) {
+fn init_background(
+    world: &mut World,
+    dimensions: &ScreenDimensions,
+    bg_sprite_sheet_handle: Handle<SpriteSheet>,
+) -> Entity {
     let rotation = UnitQuaternion::from_euler_angles(0.0, 0.0, 0.0);
 
     let scale = Vector3::new(1.0, 1.0, 1.0);
@@ -269,78 +548,317 @@ fn init_background(world: &mut World, dimensions: &ScreenDimensions, bg_sprite_s
         sprite_number: 0,
     };
 
-    world.create_entity().with(bg_render).with(transform).build();
+    world
+        .create_entity()
+        .with(bg_render)
+        .with(transform)
+        .with(CleanupTag {})
+        .build()
+}
+
+// creates the boss health bar at the top of the screen. the fill is anchored on
+// its left edge so `BossHealthBarSystem` can shrink it by changing the width
+fn init_boss_health_bar(world: &mut World) {
+    let full_width = 800.0;
+    let height = 24.0;
+
+    let background_transform = UiTransform::new(
+        "boss_health_background".to_string(),
+        Anchor::TopMiddle,
+        Anchor::Middle,
+        0.0,
+        -60.0,
+        1.0,
+        full_width,
+        height,
+    );
+
+    let fill_transform = UiTransform::new(
+        "boss_health_fill".to_string(),
+        Anchor::TopMiddle,
+        Anchor::MiddleLeft,
+        -full_width * 0.5,
+        -60.0,
+        2.0,
+        full_width,
+        height,
+    );
+
+    world
+        .create_entity()
+        .with(background_transform)
+        .with(UiImage::SolidColor([0.2, 0.2, 0.2, 0.8]))
+        .with(CleanupTag {})
+        .build();
+
+    world
+        .create_entity()
+        .with(fill_transform)
+        .with(UiImage::SolidColor([0.8, 0.1, 0.1, 1.0]))
+        .with(BossHealthBar { full_width })
+        .with(CleanupTag {})
+        .build();
+}
+
+// creates a lives and score readout in the top corners for each player in
+// this game that has a spawn point in the level
+fn init_player_huds(world: &mut World, level_metadata: &LevelMetadata) {
+    let font = {
+        let loader = world.read_resource::<Loader>();
+        let font_storage = world.read_resource::<AssetStorage<FontAsset>>();
+        get_default_font(&loader, &font_storage)
+    };
+
+    let mut indexes: Vec<usize> = {
+        let player_stats = world.read_resource::<PlayerStats>();
+        level_metadata
+            .get_layout()
+            .iter()
+            .filter_map(|(entity_type, _x, _y)| match entity_type {
+                EntityType::Player(index) if player_stats.is_playing(*index) => Some(*index),
+                _ => None,
+            })
+            .collect()
+    };
+    indexes.sort();
+    indexes.dedup();
+
+    for index in indexes {
+        // player one on the left, everyone else stacked on the right
+        let (anchor, x, y) = if index == 0 {
+            (Anchor::TopLeft, 220.0, -30.0)
+        } else {
+            (Anchor::TopRight, -220.0, -30.0 - (index - 1) as f32 * 40.0)
+        };
+
+        let transform =
+            UiTransform::new(format!("player_hud_{}", index), anchor, Anchor::Middle, x, y, 1.0, 400.0, 40.0);
+        let mut text = UiText::new(font.clone(), String::new(), [1.0, 1.0, 1.0, 1.0], 28.0);
+        text.line_mode = LineMode::Single;
+        text.align = Anchor::Middle;
+
+        world
+            .create_entity()
+            .with(transform)
+            .with(text)
+            .with(PlayerHud { index })
+            .with(CleanupTag {})
+            .build();
+    }
 }
 
 // takes the current level metadata and gameplay handles, then adds
 // all the associated entities and components to the world
 fn init_level(world: &mut World, level_metadata: LevelMetadata, handles: GameplayHandles) {
+    for rec in level_metadata.get_layout() {
+        let (entity_type, x, y) = rec;
+        spawn_level_entity(world, &handles, entity_type, *x, *y);
+    }
+}
+
+// creates one entity from the level layout, using the prefab and sprite for
+// its entity type
+fn spawn_level_entity(world: &mut World, handles: &GameplayHandles, entity_type: &EntityType, x: f32, y: f32) {
     let rotation = UnitQuaternion::from_euler_angles(0.0, 0.0, 0.0);
     let scale = Vector3::new(0.25, 0.25, 0.25);
+    let position = Translation3::new(x, y, 0.0);
+    let transform = Transform::new(position, rotation, scale);
+    let cleanup_tag = CleanupTag {};
 
-    let player_render = SpriteRender {
-        sprite_sheet: handles.player_sprites_handle,
-        sprite_number: 0,
+    // players and hazards are told apart by color, so they follow the palette setting
+    let (colorblind, (damage_color, slow_color, teleporter_color)) = {
+        let settings = world.read_resource::<Settings>();
+        (settings.colorblind, settings.hazard_colors())
     };
 
-    let boss_render = SpriteRender {
-        sprite_sheet: handles.enemy_sprites_handle.clone(),
+    match entity_type {
+        EntityType::Boss => {
+            let boss_render = SpriteRender {
+                sprite_sheet: handles.enemy_sprites_handle.clone(),
+                sprite_number: 0,
+            };
+            world
+                .create_entity()
+                .with(handles.boss_prefab_handle.clone())
+                .with(SpawnPoint { x, y })
+                .with(boss_render)
+                .with(transform)
+                .with(cleanup_tag)
+                .build();
+        },
+        EntityType::SquareEnemy => {
+            let square_render = SpriteRender {
+                sprite_sheet: handles.enemy_sprites_handle.clone(),
+                sprite_number: 1,
+            };
+            world
+                .create_entity()
+                .with(handles.enemy_prefab_handle.clone())
+                .with(SpawnPoint { x, y })
+                .with(square_render)
+                .with(transform)
+                .with(cleanup_tag)
+                .build();
+        },
+        EntityType::FlyingEnemy => {
+            let flying_render = SpriteRender {
+                sprite_sheet: handles.enemy_sprites_handle.clone(),
+                sprite_number: 2,
+            };
+            world
+                .create_entity()
+                .with(handles.flying_enemy_prefab_handle.clone())
+                .with(SpawnPoint { x, y })
+                .with(flying_render)
+                .with(transform)
+                .with(cleanup_tag)
+                .build();
+        },
+        // hunters share the square enemy's sprite
+        EntityType::HunterEnemy => {
+            let hunter_render = SpriteRender {
+                sprite_sheet: handles.enemy_sprites_handle.clone(),
+                sprite_number: 1,
+            };
+            world
+                .create_entity()
+                .with(handles.hunter_enemy_prefab_handle.clone())
+                .with(SpawnPoint { x, y })
+                .with(hunter_render)
+                .with(transform)
+                .with(cleanup_tag)
+                .build();
+        },
+        // walls reuse the plain black overlay sprite (32 pixels square), stretched
+        // to fill a grid cell
+        EntityType::Wall => {
+            let wall_render = SpriteRender {
+                sprite_sheet: handles.overlay_sprite_handle.clone(),
+                sprite_number: 0,
+            };
+            let scale = Vector3::new(
+                WALL_COLLIDER.half_width * 2.0 / 32.0,
+                WALL_COLLIDER.half_height * 2.0 / 32.0,
+                1.0,
+            );
+            let wall_transform = Transform::new(position, rotation, scale);
+
+            world
+                .create_entity()
+                .with(wall_render)
+                .with(wall_transform)
+                .with(WALL_COLLIDER)
+                .with(Tint(Srgba::new(1.0, 1.0, 1.0, 0.85)))
+                .with(Transparent)
+                .with(cleanup_tag)
+                .build();
+        },
+        EntityType::DamageZone => {
+            hazard_tile(world, handles, position, damage_color)
+                .with(DamageZone::default())
+                .with(cleanup_tag)
+                .build();
+        },
+        EntityType::SlowZone => {
+            hazard_tile(world, handles, position, slow_color)
+                .with(SlowZone::default())
+                .with(cleanup_tag)
+                .build();
+        },
+        EntityType::Teleporter {
+            destination_x,
+            destination_y,
+        } => {
+            let teleporter = Teleporter {
+                destination_x: *destination_x,
+                destination_y: *destination_y,
+            };
+            hazard_tile(world, handles, position, teleporter_color)
+                .with(teleporter)
+                .with(cleanup_tag)
+                .build();
+        },
+        EntityType::Player(index) => {
+            // skip spawn points for players who aren't in this game or are out of lives
+            {
+                let mut player_stats = world.write_resource::<PlayerStats>();
+                if !player_stats.is_playing(*index) || player_stats.join(*index).is_out() {
+                    return;
+                }
+                player_stats.spawned(*index);
+            }
+
+            let player_render = SpriteRender {
+                sprite_sheet: handles.player_sprites_handle.clone(),
+                sprite_number: 0,
+            };
+            world
+                .create_entity()
+                .with(handles.player_prefab_handle.clone())
+                .with(PlayerIndex(*index))
+                .with(player_tint(*index, colorblind))
+                .with(player_render)
+                .with(transform)
+                .with(cleanup_tag)
+                .build();
+        },
+    }
+}
+
+// starts building a hazard: the plain white tile sprite (32 pixels square)
+// stretched to fill a grid cell and tinted to show what it does
+fn hazard_tile<'a>(
+    world: &'a mut World,
+    handles: &GameplayHandles,
+    position: Translation3<f32>,
+    color: Srgba,
+) -> EntityBuilder<'a> {
+    let tile_render = SpriteRender {
+        sprite_sheet: handles.tile_sprite_handle.clone(),
         sprite_number: 0,
     };
+    let scale = Vector3::new(
+        HAZARD_COLLIDER.half_width * 2.0 / 32.0,
+        HAZARD_COLLIDER.half_height * 2.0 / 32.0,
+        1.0,
+    );
+    let rotation = UnitQuaternion::from_euler_angles(0.0, 0.0, 0.0);
 
-    let square_render = SpriteRender {
-        sprite_sheet: handles.enemy_sprites_handle.clone(),
-        sprite_number: 1,
-    };
+    world
+        .create_entity()
+        .with(tile_render)
+        .with(Transform::new(position, rotation, scale))
+        .with(HAZARD_COLLIDER)
+        .with(Tint(color))
+        .with(Transparent)
+}
 
-    let flying_render = SpriteRender {
-        sprite_sheet: handles.enemy_sprites_handle,
-        sprite_number: 2,
-    };
+// converts the mouse position from screen coordinates to world coordinates
+// using the camera created in `init_camera`
+#[cfg(feature = "debug-tools")]
+fn cursor_world_position(world: &World) -> Option<(f32, f32)> {
+    let input = world.read_resource::<InputHandler<StringBindings>>();
+    let (mouse_x, mouse_y) = input.mouse_position()?;
+
+    let dimensions = world.read_resource::<ScreenDimensions>();
+    let cameras = world.read_storage::<Camera>();
+    let transforms = world.read_storage::<Transform>();
+
+    (&cameras, &transforms)
+        .join()
+        .next()
+        .map(|(camera, camera_transform)| screen_to_world(mouse_x, mouse_y, &dimensions, camera, camera_transform))
+}
 
-    for rec in level_metadata.get_layout() {
-        let (entity_type, x, y) = rec;
-        let cleanup_tag = CleanupTag {};
-        let position = Translation3::new(*x, *y, 0.0);
-        let transform = Transform::new(position, rotation, scale);
-
-        match entity_type {
-            EntityType::Boss => {
-                world
-                    .create_entity()
-                    .with(handles.boss_prefab_handle.clone())
-                    .with(boss_render.clone())
-                    .with(transform)
-                    .with(cleanup_tag)
-                    .build();
-            },
-            EntityType::SquareEnemy => {
-                world
-                    .create_entity()
-                    .with(handles.enemy_prefab_handle.clone())
-                    .with(square_render.clone())
-                    .with(transform)
-                    .with(cleanup_tag)
-                    .build();
-            },
-            EntityType::FlyingEnemy => {
-                world
-                    .create_entity()
-                    .with(handles.flying_enemy_prefab_handle.clone())
-                    .with(flying_render.clone())
-                    .with(transform)
-                    .with(cleanup_tag)
-                    .build();
-            },
-            EntityType::Player => {
-                world
-                    .create_entity()
-                    .with(handles.player_prefab_handle.clone())
-                    .with(player_render.clone())
-                    .with(transform)
-                    .with(cleanup_tag)
-                    .build();
-            },
-        }
+// deletes every enemy in the current level, which will trigger the usual
+// level complete transition in `update`
+#[cfg(feature = "debug-tools")]
+fn kill_all_enemies(world: &mut World) {
+    let entities = world.read_resource::<EntitiesRes>();
+    let enemies = world.read_storage::<Enemy>();
+
+    for (entity, _enemy) in (&entities, &enemies).join() {
+        let err = format!("unable to delete entity: {:?}", entity);
+        entities.delete(entity).expect(&err);
     }
 }
@@ -0,0 +1,102 @@
+/// The title screen. This is the first state the application runs, and the
+/// pause menu can switch back to it. The levels are loaded once by `main.rs`
+/// and stored as a resource, so each new game starts from the first level.
+/// Starting a game also resets every player's score, and their lives to the
+/// number the difficulty setting gives them.
+use amethyst::{
+    input::{is_close_requested, is_key_down, VirtualKeyCode},
+    prelude::*,
+};
+
+use crate::{
+    resources::{
+        audio::play_music,
+        level::Levels,
+        menu::Menu,
+        players::PlayerStats,
+        settings::{apply_settings, Settings},
+    },
+    states::{gameplay::GameplayState, options::OptionsState, rebind::RebindState},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MainMenuOption {
+    StartGame,
+    TwoPlayers,
+    Controls,
+    Options,
+    Quit,
+}
+
+#[derive(Default)]
+pub struct MainMenuState {
+    menu: Option<Menu<MainMenuOption>>,
+}
+
+impl SimpleState for MainMenuState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        // the window doesn't exist until the game is running, so this is the
+        // first chance to apply settings like fullscreen
+        apply_settings(data.world);
+
+        // the title screen is quiet, even when a game is quit back to it
+        play_music(data.world, None);
+        self.menu = Some(create_menu(data.world));
+    }
+
+    // hide the menu text while another menu is pushed on top
+    fn on_pause(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(mut menu) = self.menu.take() {
+            menu.delete(data.world);
+        }
+    }
+
+    fn on_resume(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        self.menu = Some(create_menu(data.world));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(mut menu) = self.menu.take() {
+            menu.delete(data.world);
+        }
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(&event) || is_key_down(&event, VirtualKeyCode::Escape) {
+                return Trans::Quit;
+            }
+
+            let selection = self.menu.as_mut().and_then(|menu| menu.handle_event(data.world, &event));
+
+            match selection {
+                Some(MainMenuOption::StartGame) => return start_game(data.world, 1),
+                Some(MainMenuOption::TwoPlayers) => return start_game(data.world, 2),
+                Some(MainMenuOption::Controls) => return Trans::Push(Box::new(RebindState::default())),
+                Some(MainMenuOption::Options) => return Trans::Push(Box::new(OptionsState::default())),
+                Some(MainMenuOption::Quit) => return Trans::Quit,
+                None => {},
+            }
+        }
+
+        Trans::None
+    }
+}
+
+fn create_menu(world: &mut World) -> Menu<MainMenuOption> {
+    Menu::new(world, vec![
+        (MainMenuOption::StartGame, "Start Game".to_string()),
+        (MainMenuOption::TwoPlayers, "Two Players".to_string()),
+        (MainMenuOption::Controls, "Controls".to_string()),
+        (MainMenuOption::Options, "Options".to_string()),
+        (MainMenuOption::Quit, "Quit".to_string()),
+    ])
+}
+
+fn start_game(world: &mut World, player_count: usize) -> SimpleTrans {
+    let starting_lives = world.read_resource::<Settings>().difficulty.starting_lives();
+    world.insert(PlayerStats::new(player_count, starting_lives));
+
+    let levels = (*world.read_resource::<Levels>()).clone();
+    Trans::Switch(Box::new(GameplayState::new(levels)))
+}
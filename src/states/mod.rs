@@ -1,5 +1,9 @@
-pub use self::{gameplay::GameplayState, paused::PausedState};
+pub use self::menu::MainMenuState;
 
+mod gameover;
 mod gameplay;
+mod menu;
+mod options;
 mod paused;
+mod rebind;
 mod transition;
@@ -0,0 +1,224 @@
+/// An options screen that can be pushed from the main menu or the pause menu.
+/// Enter or the right arrow changes the selected setting and the left arrow
+/// changes it back the other way. Saving applies the settings and writes
+/// them to the user settings file (see `resources::settings`), which
+/// `main.rs` merges over the shipped defaults on the next start.
+use amethyst::{
+    input::{is_close_requested, is_key_down, VirtualKeyCode},
+    prelude::*,
+    utils::application_root_dir,
+};
+
+use ron::ser::PrettyConfig;
+
+use std::fs;
+
+use crate::resources::{
+    menu::Menu,
+    settings::{apply_settings, Settings, RESOLUTIONS},
+};
+
+use log::{info, warn};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SettingOption {
+    Fullscreen,
+    Resolution,
+    FrameCap,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    ScreenShake,
+    Colorblind,
+    Difficulty,
+    Save,
+    Back,
+}
+
+const SETTINGS: [SettingOption; 9] = [
+    SettingOption::Fullscreen,
+    SettingOption::Resolution,
+    SettingOption::FrameCap,
+    SettingOption::MasterVolume,
+    SettingOption::MusicVolume,
+    SettingOption::SfxVolume,
+    SettingOption::ScreenShake,
+    SettingOption::Colorblind,
+    SettingOption::Difficulty,
+];
+
+// volumes change in tenths
+const VOLUME_STEPS: i32 = 10;
+
+#[derive(Default)]
+pub struct OptionsState {
+    // a working copy of the settings, only applied if the player saves
+    settings: Settings,
+
+    menu: Option<Menu<SettingOption>>,
+}
+
+impl SimpleState for OptionsState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+
+        self.settings = (*world.read_resource::<Settings>()).clone();
+
+        let mut items: Vec<(SettingOption, String)> =
+            SETTINGS.iter().map(|option| (*option, self.label(*option))).collect();
+        items.push((SettingOption::Save, "Save".to_string()));
+        items.push((SettingOption::Back, "Back".to_string()));
+
+        self.menu = Some(Menu::new(world, items));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(mut menu) = self.menu.take() {
+            menu.delete(data.world);
+        }
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(&event) {
+                return Trans::Quit;
+            }
+
+            if is_key_down(&event, VirtualKeyCode::Escape) {
+                return Trans::Pop;
+            }
+
+            // left and right step through a setting's values without leaving it
+            let step = if is_key_down(&event, VirtualKeyCode::Left) {
+                Some(-1)
+            } else if is_key_down(&event, VirtualKeyCode::Right) {
+                Some(1)
+            } else {
+                None
+            };
+
+            if let Some(direction) = step {
+                if let Some(option) = self.menu.as_ref().map(Menu::selected) {
+                    self.change(data.world, option, direction);
+                }
+                return Trans::None;
+            }
+
+            let selection = self.menu.as_mut().and_then(|menu| menu.handle_event(data.world, &event));
+
+            match selection {
+                Some(SettingOption::Save) => {
+                    self.save(data.world);
+                    return Trans::Pop;
+                },
+                Some(SettingOption::Back) => return Trans::Pop,
+                Some(option) => self.change(data.world, option, 1),
+                None => {},
+            }
+        }
+
+        Trans::None
+    }
+}
+
+impl OptionsState {
+    fn label(&self, option: SettingOption) -> String {
+        let settings = &self.settings;
+
+        match option {
+            SettingOption::Fullscreen => format!("Fullscreen: {}", on_off(settings.fullscreen)),
+            SettingOption::Resolution => {
+                let (width, height) = settings.resolution;
+                format!("Resolution: {}x{}", width, height)
+            },
+            SettingOption::FrameCap => format!("Frame cap: {}", on_off(settings.frame_cap)),
+            SettingOption::MasterVolume => format!("Master volume: {}", percent(settings.master_volume)),
+            SettingOption::MusicVolume => format!("Music volume: {}", percent(settings.music_volume)),
+            SettingOption::SfxVolume => format!("Sound effects volume: {}", percent(settings.sfx_volume)),
+            SettingOption::ScreenShake => format!("Screen shake: {}", on_off(settings.screen_shake)),
+            SettingOption::Colorblind => format!("Colorblind palette: {}", on_off(settings.colorblind)),
+            SettingOption::Difficulty => format!("Difficulty: {:?}", settings.difficulty),
+            SettingOption::Save => "Save".to_string(),
+            SettingOption::Back => "Back".to_string(),
+        }
+    }
+
+    // moves a setting one step in `direction` (1 or -1). on/off settings just
+    // toggle, and everything else wraps around at either end
+    fn change(&mut self, world: &mut World, option: SettingOption, direction: i32) {
+        let settings = &mut self.settings;
+
+        match option {
+            SettingOption::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            SettingOption::Resolution => {
+                let current = RESOLUTIONS.iter().position(|resolution| *resolution == settings.resolution);
+                let next = match current {
+                    Some(index) => (index as i32 + direction).rem_euclid(RESOLUTIONS.len() as i32),
+                    // a size from the display config that isn't in the list
+                    None => 0,
+                };
+                settings.resolution = RESOLUTIONS[next as usize];
+            },
+            SettingOption::FrameCap => settings.frame_cap = !settings.frame_cap,
+            SettingOption::MasterVolume => settings.master_volume = step_volume(settings.master_volume, direction),
+            SettingOption::MusicVolume => settings.music_volume = step_volume(settings.music_volume, direction),
+            SettingOption::SfxVolume => settings.sfx_volume = step_volume(settings.sfx_volume, direction),
+            SettingOption::ScreenShake => settings.screen_shake = !settings.screen_shake,
+            SettingOption::Colorblind => settings.colorblind = !settings.colorblind,
+            SettingOption::Difficulty => {
+                // there are three difficulties, so going back is going forward twice
+                let steps = if direction < 0 { 2 } else { 1 };
+                for _ in 0 .. steps {
+                    settings.difficulty = settings.difficulty.next();
+                }
+            },
+            SettingOption::Save | SettingOption::Back => return,
+        }
+
+        if let Some(menu) = self.menu.as_ref() {
+            menu.set_label(world, option, self.label(option));
+        }
+    }
+
+    fn save(&self, world: &mut World) {
+        world.insert(self.settings.clone());
+        apply_settings(world);
+
+        let config_dir = match application_root_dir() {
+            Ok(app_root) => app_root.join("config"),
+            Err(err) => {
+                warn!("unable to find the config directory: {}", err);
+                return;
+            },
+        };
+
+        let path = Settings::user_settings_path(&config_dir);
+
+        let saved = ron::ser::to_string_pretty(&self.settings.to_user_settings(), PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| fs::write(&path, contents).map_err(|err| err.to_string()));
+
+        match saved {
+            Ok(()) => info!("saved settings to {:?}", path),
+            Err(err) => warn!("unable to save settings to {:?}: {}", path, err),
+        }
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+fn percent(volume: f32) -> String {
+    format!("{}%", (volume * 100.0).round() as i32)
+}
+
+// one step up or down, wrapping between silent and full volume
+fn step_volume(volume: f32, direction: i32) -> f32 {
+    let step = (volume * VOLUME_STEPS as f32).round() as i32;
+    (step + direction).rem_euclid(VOLUME_STEPS + 1) as f32 / VOLUME_STEPS as f32
+}
@@ -1,24 +1,160 @@
 /// The `states::gameplay::GameplayState` module uses a dispatcher to ensure
 /// its systems only run when it is the active state. Consequently, this
 /// paused state doesn't need to do anything to stop all the game action.
-/// Eventually though it'd be good to have an overplay or "press p to continue"
-/// message.
+/// It dims the screen with an overlay and shows a small menu for resuming,
+/// restarting the level, changing controls or options, or quitting to the
+/// main menu.
 use amethyst::{
-    input::{is_key_down, VirtualKeyCode},
+    assets::Handle,
+    core::math::{Translation3, UnitQuaternion, Vector3},
+    core::transform::Transform,
+    ecs::prelude::Entity,
+    input::{is_close_requested, is_key_down, VirtualKeyCode},
     prelude::*,
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, SpriteSheet, Transparent},
+    window::ScreenDimensions,
 };
 
-pub struct PausedState;
+use derive_new::new;
+
+use crate::{
+    resources::{level::Levels, menu::Menu},
+    states::{gameplay::GameplayState, menu::MainMenuState, options::OptionsState, rebind::RebindState},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PauseOption {
+    Resume,
+    RestartLevel,
+    Controls,
+    Options,
+    QuitToMenu,
+}
+
+#[derive(new)]
+pub struct PausedState {
+    overlay_sprite_handle: Handle<SpriteSheet>,
+
+    // the levels to rebuild if the player restarts. the level being
+    // restarted should be the last item, since `GameplayState` pops levels
+    restart_levels: Levels,
+
+    #[new(default)]
+    overlay: Option<Entity>,
+
+    #[new(default)]
+    menu: Option<Menu<PauseOption>>,
+}
 
-// wait for someone to press the P key so we can get back to the game
 impl SimpleState for PausedState {
-    fn handle_event(&mut self, _data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+
+        let dimensions = (*world.read_resource::<ScreenDimensions>()).clone();
+        self.overlay = Some(init_overlay(world, &dimensions, self.overlay_sprite_handle.clone()));
+
+        self.menu = Some(create_menu(world));
+    }
+
+    // other menus can be pushed on top of this one, so hide the menu text
+    // while they're active
+    fn on_pause(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(mut menu) = self.menu.take() {
+            menu.delete(data.world);
+        }
+    }
+
+    fn on_resume(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        self.menu = Some(create_menu(data.world));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(mut menu) = self.menu.take() {
+            menu.delete(data.world);
+        }
+
+        if let Some(overlay) = self.overlay.take() {
+            let err = format!("unable to delete entity: {:?}", overlay);
+            data.world.delete_entity(overlay).expect(&err);
+        }
+    }
+
+    // wait for someone to press P (or pick something from the menu) so we
+    // can get back to the game
+    fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
         if let StateEvent::Window(event) = &event {
-            if is_key_down(&event, VirtualKeyCode::P) {
+            if is_close_requested(&event) {
+                return Trans::Quit;
+            }
+
+            if is_key_down(&event, VirtualKeyCode::P) || is_key_down(&event, VirtualKeyCode::Escape) {
                 return Trans::Pop;
             }
+
+            let selection = self.menu.as_mut().and_then(|menu| menu.handle_event(data.world, &event));
+
+            if let Some(option) = selection {
+                return self.select(option);
+            }
         }
         // if the user hasn't pressed P, keep this state active
         Trans::None
     }
 }
+
+impl PausedState {
+    fn select(&mut self, option: PauseOption) -> SimpleTrans {
+        match option {
+            PauseOption::Resume => Trans::Pop,
+            // popping first ensures the paused state is stopped before the
+            // underlying state is replaced and cleans up its entities
+            PauseOption::RestartLevel => Trans::Sequence(vec![
+                Trans::Pop,
+                Trans::Switch(Box::new(GameplayState::new(self.restart_levels.clone()))),
+            ]),
+            PauseOption::Controls => Trans::Push(Box::new(RebindState::default())),
+            PauseOption::Options => Trans::Push(Box::new(OptionsState::default())),
+            PauseOption::QuitToMenu => Trans::Sequence(vec![
+                Trans::Pop,
+                Trans::Switch(Box::new(MainMenuState::default())),
+            ]),
+        }
+    }
+}
+
+fn create_menu(world: &mut World) -> Menu<PauseOption> {
+    Menu::new(world, vec![
+        (PauseOption::Resume, "Resume".to_string()),
+        (PauseOption::RestartLevel, "Restart Level".to_string()),
+        (PauseOption::Controls, "Controls".to_string()),
+        (PauseOption::Options, "Options".to_string()),
+        (PauseOption::QuitToMenu, "Quit to Menu".to_string()),
+    ])
+}
+
+// dims everything under the menu. this uses the same sprite as the level
+// transition overlay, just with a fixed alpha instead of a fader
+fn init_overlay(
+    world: &mut World,
+    dimensions: &ScreenDimensions,
+    overlay_sprite_handle: Handle<SpriteSheet>,
+) -> Entity {
+    let rotation = UnitQuaternion::from_euler_angles(0.0, 0.0, 0.0);
+
+    let scale = Vector3::new(100.0, 100.0, 1.0);
+    let position = Translation3::new(dimensions.width() * 0.5, dimensions.height() * 0.5, 5.0);
+    let transform = Transform::new(position, rotation, scale);
+    let tint = Tint(Srgba::new(0.0, 0.0, 0.0, 0.6));
+    let overlay_render = SpriteRender {
+        sprite_sheet: overlay_sprite_handle,
+        sprite_number: 0,
+    };
+
+    world
+        .create_entity()
+        .with(overlay_render)
+        .with(transform)
+        .with(Transparent)
+        .with(tint)
+        .build()
+}
@@ -0,0 +1,223 @@
+/// A controls screen that can be pushed from the main menu or the pause
+/// menu. Selecting a binding waits for the next key press and assigns it.
+/// Saving applies the bindings immediately and writes them to the user
+/// bindings file (see `resources::controls`), which `main.rs` prefers over
+/// the shipped defaults on the next start.
+use amethyst::{
+    input::{is_close_requested, is_key_down, Axis, Bindings, Button, InputHandler, StringBindings, VirtualKeyCode},
+    prelude::*,
+    utils::application_root_dir,
+    winit::{ElementState, Event, KeyboardInput, WindowEvent},
+};
+
+use ron::ser::PrettyConfig;
+
+use std::fs;
+
+use crate::resources::{controls::ControlsConfig, menu::Menu};
+
+use log::{info, warn};
+
+// axis name, whether this is the positive side of the axis, and the label
+// to display. the axis names are the ones used in `config/bindings.ron`
+const REBINDABLE: [(&str, bool, &str); 16] = [
+    ("y_axis", true, "Move up"),
+    ("y_axis", false, "Move down"),
+    ("x_axis", false, "Move left"),
+    ("x_axis", true, "Move right"),
+    ("y_laser", true, "Fire up"),
+    ("y_laser", false, "Fire down"),
+    ("x_laser", false, "Fire left"),
+    ("x_laser", true, "Fire right"),
+    ("y_axis_p2", true, "P2 move up"),
+    ("y_axis_p2", false, "P2 move down"),
+    ("x_axis_p2", false, "P2 move left"),
+    ("x_axis_p2", true, "P2 move right"),
+    ("y_laser_p2", true, "P2 fire up"),
+    ("y_laser_p2", false, "P2 fire down"),
+    ("x_laser_p2", false, "P2 fire left"),
+    ("x_laser_p2", true, "P2 fire right"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RebindOption {
+    Binding(usize),
+    Save,
+    Back,
+}
+
+#[derive(Default)]
+pub struct RebindState {
+    // a working copy of the bindings, only applied if the player saves
+    bindings: Bindings<StringBindings>,
+
+    // the binding waiting for a key press, if any
+    waiting_for: Option<usize>,
+
+    menu: Option<Menu<RebindOption>>,
+}
+
+impl SimpleState for RebindState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+
+        self.bindings = world.read_resource::<InputHandler<StringBindings>>().bindings.clone();
+
+        let mut items: Vec<(RebindOption, String)> = (0 .. REBINDABLE.len())
+            .map(|index| (RebindOption::Binding(index), self.binding_label(index)))
+            .collect();
+        items.push((RebindOption::Save, "Save".to_string()));
+        items.push((RebindOption::Back, "Back".to_string()));
+
+        self.menu = Some(Menu::new(world, items));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(mut menu) = self.menu.take() {
+            menu.delete(data.world);
+        }
+    }
+
+    fn handle_event(&mut self, data: StateData<'_, GameData<'_, '_>>, event: StateEvent) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(&event) {
+                return Trans::Quit;
+            }
+
+            // the next key press is the new binding (escape cancels)
+            if let Some(index) = self.waiting_for {
+                if let Some(key) = pressed_key(&event) {
+                    if key != VirtualKeyCode::Escape {
+                        self.rebind(index, key);
+                    }
+                    self.waiting_for = None;
+                    self.refresh_label(data.world, index);
+                }
+                return Trans::None;
+            }
+
+            if is_key_down(&event, VirtualKeyCode::Escape) {
+                return Trans::Pop;
+            }
+
+            let selection = self.menu.as_mut().and_then(|menu| menu.handle_event(data.world, &event));
+
+            match selection {
+                Some(RebindOption::Binding(index)) => {
+                    self.waiting_for = Some(index);
+                    if let Some(menu) = self.menu.as_ref() {
+                        let label = format!("{}: press a key", REBINDABLE[index].2);
+                        menu.set_label(data.world, RebindOption::Binding(index), label);
+                    }
+                },
+                Some(RebindOption::Save) => {
+                    self.save(data.world);
+                    return Trans::Pop;
+                },
+                Some(RebindOption::Back) => return Trans::Pop,
+                None => {},
+            }
+        }
+
+        Trans::None
+    }
+}
+
+impl RebindState {
+    fn binding_label(&self, index: usize) -> String {
+        let (axis_name, positive, label) = REBINDABLE[index];
+
+        let button = match self.bindings.axis(axis_name) {
+            Some(Axis::Emulated { pos, neg }) => Some(if positive { *pos } else { *neg }),
+            _ => None,
+        };
+
+        match button {
+            Some(Button::Key(key)) => format!("{}: {:?}", label, key),
+            Some(other) => format!("{}: {:?}", label, other),
+            // e.g. the gamepad profile binds sticks instead of keys
+            None => format!("{}: not a key binding", label),
+        }
+    }
+
+    fn refresh_label(&self, world: &mut World, index: usize) {
+        if let Some(menu) = self.menu.as_ref() {
+            menu.set_label(world, RebindOption::Binding(index), self.binding_label(index));
+        }
+    }
+
+    // replaces one side of an emulated axis. amethyst rejects keys that are
+    // already bound elsewhere, in which case the old binding is kept
+    fn rebind(&mut self, index: usize, key: VirtualKeyCode) {
+        let (axis_name, positive, _label) = REBINDABLE[index];
+
+        let new_axis = match self.bindings.axis(axis_name) {
+            Some(Axis::Emulated { neg, .. }) if positive => Axis::Emulated {
+                pos: Button::Key(key),
+                neg: *neg,
+            },
+            Some(Axis::Emulated { pos, .. }) => Axis::Emulated {
+                pos: *pos,
+                neg: Button::Key(key),
+            },
+            _ => {
+                info!("{} isn't bound to keys, so it can't be rebound here", axis_name);
+                return;
+            },
+        };
+
+        let old_axis = self.bindings.remove_axis(axis_name);
+
+        if let Err(err) = self.bindings.insert_axis(axis_name, new_axis) {
+            warn!("unable to bind {:?} to {}: {}", key, axis_name, err);
+
+            if let Some(axis) = old_axis {
+                self.bindings
+                    .insert_axis(axis_name, axis)
+                    .expect("unable to restore the previous binding");
+            }
+        }
+    }
+
+    fn save(&self, world: &mut World) {
+        world.write_resource::<InputHandler<StringBindings>>().bindings = self.bindings.clone();
+
+        let config_dir = match application_root_dir() {
+            Ok(app_root) => app_root.join("config"),
+            Err(err) => {
+                warn!("unable to find the config directory: {}", err);
+                return;
+            },
+        };
+
+        let path = world.read_resource::<ControlsConfig>().user_bindings_path(&config_dir);
+
+        let saved = ron::ser::to_string_pretty(&self.bindings, PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| fs::write(&path, contents).map_err(|err| err.to_string()));
+
+        match saved {
+            Ok(()) => info!("saved bindings to {:?}", path),
+            Err(err) => warn!("unable to save bindings to {:?}: {}", path, err),
+        }
+    }
+}
+
+fn pressed_key(event: &Event) -> Option<VirtualKeyCode> {
+    match event {
+        Event::WindowEvent {
+            event:
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            virtual_keycode: Some(key),
+                            state: ElementState::Pressed,
+                            ..
+                        },
+                    ..
+                },
+            ..
+        } => Some(*key),
+        _ => None,
+    }
+}
@@ -17,7 +17,9 @@ use amethyst::{
 use derive_new::new;
 
 use crate::{
+    components::cleanup::CleanupTag,
     resources::{
+        audio::{SoundEffect, SoundQueue},
         fade::{Fade, FadeStatus, Fader},
         level::Levels,
     },
@@ -69,6 +71,8 @@ impl<'a, 'b> SimpleState for TransitionState<'a, 'b> {
         // initialize the overlay image
         let dimensions = (*world.read_resource::<ScreenDimensions>()).clone();
         init_overlay(world, &dimensions, self.overlay_sprite_handle.clone());
+
+        world.write_resource::<SoundQueue>().play(SoundEffect::LevelTransition);
     }
 
     fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
@@ -88,13 +92,13 @@ impl<'a, 'b> SimpleState for TransitionState<'a, 'b> {
     }
 
     fn on_stop(&mut self, data: StateData<GameData>) {
-        // state items that should be cleaned up (players, entities, lasers,
-        // projectiles) should all be marked with `CleanupTag` and removed
-        // here when this state ends
+        // removes the overlay, and the finished level's camera and background
+        // (the only things `GameplayState` leaves tagged with `CleanupTag`)
         let entities = data.world.read_resource::<EntitiesRes>();
         let faders = data.world.read_storage::<Fader>();
+        let cleanup_tags = data.world.read_storage::<CleanupTag>();
 
-        for (entity, _tag) in (&entities, &faders).join() {
+        for (entity, _tag) in (&entities, faders.mask() | cleanup_tags.mask()).join() {
             let err = format!("unable to delete entity: {:?}", entity);
             entities.delete(entity).expect(&err);
         }
@@ -109,7 +113,11 @@ impl<'a, 'b> SimpleState for TransitionState<'a, 'b> {
             }
 
             if is_key_down(&event, VirtualKeyCode::P) {
-                return Trans::Push(Box::new(PausedState));
+                // restarting from here starts the upcoming level
+                return Trans::Push(Box::new(PausedState::new(
+                    self.overlay_sprite_handle.clone(),
+                    self.levels.clone(),
+                )));
             }
         }
 
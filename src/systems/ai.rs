@@ -0,0 +1,102 @@
+use amethyst::{
+    core::{timing::Time, Transform},
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, WriteStorage},
+};
+
+use crate::{
+    components::{
+        ai::{Ai, Senses},
+        launcher::Launcher,
+        movement::Movement,
+        targeting::{player_positions, Targeting},
+    },
+    entities::{
+        enemy::Enemy,
+        player::{Player, PlayerIndex},
+    },
+    resources::walls::Walls,
+};
+
+use log::info;
+
+// runs each enemy's AI state machine. entering a state swaps the enemy's
+// movement strategy and turns its launcher on or off, so this needs to run
+// before `MovementTrackingSystem` picks the next move
+#[derive(SystemDesc)]
+pub struct AiSystem;
+
+impl<'s> System<'s> for AiSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        WriteStorage<'s, Ai>,
+        WriteStorage<'s, Movement>,
+        WriteStorage<'s, Launcher>,
+        ReadStorage<'s, Enemy>,
+        ReadStorage<'s, Targeting>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, PlayerIndex>,
+        ReadStorage<'s, Transform>,
+        Read<'s, Time>,
+        Read<'s, Walls>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut ais,
+            mut movements,
+            mut launchers,
+            enemies,
+            targetings,
+            players,
+            player_indexes,
+            transforms,
+            time,
+            walls,
+        ): Self::SystemData,
+    ) {
+        let player_positions = player_positions(&players, &player_indexes, &transforms);
+
+        for (ai, movement, transform, enemy, targeting, launcher) in (
+            &mut ais,
+            &mut movements,
+            &transforms,
+            (&enemies).maybe(),
+            (&targetings).maybe(),
+            (&mut launchers).maybe(),
+        )
+            .join()
+        {
+            let (x, y) = (transform.translation().x, transform.translation().y);
+
+            // the senses are about the same player the enemy is chasing
+            let target = targeting.copied().unwrap_or_default().pick_target(x, y, &player_positions);
+
+            let senses = Senses {
+                player_distance: target.map(|target| ((target.x - x).powi(2) + (target.y - y).powi(2)).sqrt()),
+                health: enemy.map(|e| e.health),
+                line_of_sight: target.map_or(false, |target| walls.line_of_sight(x, y, target.x, target.y)),
+            };
+
+            if let Some(state) = ai.update(time.delta_seconds(), &senses) {
+                info!("enemy entering ai state: {}", state.name);
+
+                movement.movement_type = state.movement_type;
+                if let Some(speed) = state.speed {
+                    movement.speed = speed;
+                }
+
+                // clear anything left over from the previous strategy
+                movement.freeze_direction = false;
+                movement.locked_direction = None;
+                movement.already_rotated = false;
+                movement.wander_target = None;
+
+                if let Some(launcher) = launcher {
+                    launcher.enabled = state.firing;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use amethyst::{
+    core::{timing::Time, Hidden},
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, World, Write, WriteStorage},
+    renderer::SpriteRender,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    components::{
+        animation::{Animation, AnimationFinished, AnimationId},
+        movement::Movement,
+    },
+    entities::player::Player,
+};
+
+// below this speed a mover counts as standing still
+const IDLE_SPEED: f32 = 1.0;
+
+// picks idle or move clips for anything that isn't in the middle of a one-shot
+// clip, then advances every animation and updates its sprite. pooled entities
+// waiting to be reused are hidden, and skipped
+#[derive(SystemDesc)]
+pub struct AnimationSystem;
+
+impl<'s> System<'s> for AnimationSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        WriteStorage<'s, Animation>,
+        WriteStorage<'s, SpriteRender>,
+        ReadStorage<'s, Movement>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Hidden>,
+        Write<'s, EventChannel<AnimationFinished>>,
+        Read<'s, Time>,
+        Entities<'s>,
+    );
+
+    fn run(
+        &mut self,
+        (mut animations, mut sprites, movements, players, hiddens, mut finished, time, entities): Self::SystemData,
+    ) {
+        for (entity, animation, sprite, movement, player, _) in (
+            &entities,
+            &mut animations,
+            &mut sprites,
+            (&movements).maybe(),
+            (&players).maybe(),
+            !&hiddens,
+        )
+            .join()
+        {
+            if !animation.is_busy() {
+                // players are moved by input rather than a `Movement`
+                let moving = player.map_or(false, |p| p.moving)
+                    || movement.map_or(false, |m| {
+                        let (x, y) = m.steered_velocity();
+                        x.hypot(y) > IDLE_SPEED
+                    });
+
+                // entities without a Move clip just keep idling
+                if moving && animation.has_clip(AnimationId::Move) {
+                    animation.play(AnimationId::Move);
+                } else {
+                    animation.play(AnimationId::Idle);
+                }
+            }
+
+            if let Some((sprite_number, just_finished)) = animation.advance(time.delta_seconds()) {
+                sprite.sprite_number = sprite_number;
+
+                if just_finished {
+                    if let Some(id) = animation.current() {
+                        finished.single_write(AnimationFinished { entity, id });
+                    }
+                }
+            }
+        }
+    }
+}
+
+// removes entities once their death animation has played out. `CollisionSystem`
+// starts Death clips for enemies that have one
+#[derive(Default)]
+pub struct AnimationCleanupSystem {
+    reader_id: Option<ReaderId<AnimationFinished>>,
+}
+
+impl<'s> System<'s> for AnimationCleanupSystem {
+    type SystemData = (Read<'s, EventChannel<AnimationFinished>>, Entities<'s>);
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader_id = Some(world.fetch_mut::<EventChannel<AnimationFinished>>().register_reader());
+    }
+
+    fn run(&mut self, (finished, entities): Self::SystemData) {
+        let reader_id = self.reader_id.as_mut().expect("AnimationCleanupSystem was not set up");
+
+        for event in finished.read(reader_id) {
+            if event.id == AnimationId::Death {
+                // the entity may already be gone if something else removed it
+                let _ = entities.delete(event.entity);
+            }
+        }
+    }
+}
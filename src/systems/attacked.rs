@@ -1,15 +1,51 @@
 use amethyst::{
-    core::Transform,
+    core::{Hidden, Transform},
     derive::SystemDesc,
-    ecs::{Entities, Join, ReadStorage, System, SystemData, WriteStorage},
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
 };
 
 use crate::{
-    components::{collider::Collider, launcher::Projectile},
-    entities::{enemy::Enemy, player::Player},
+    components::{
+        collider::Collider,
+        launcher::{recycle_projectile, Projectile},
+        movement::Movement,
+    },
+    entities::{
+        enemy::Enemy,
+        player::{Player, PlayerIndex},
+    },
+    resources::{
+        audio::{SoundEffect, SoundQueue},
+        playablearea::{PlayableArea, SHOT_CULL_MARGIN},
+        players::PlayerStats,
+        pool::Pool,
+        shake::{ScreenShake, PLAYER_DEATH_TRAUMA},
+    },
 };
+
+#[cfg(feature = "debug-tools")]
+use crate::resources::debug::DebugTools;
+
 use log::info;
 
+// god mode is a developer cheat, so shipping builds swap the debug resource
+// for an empty tuple and `god_mode_enabled` is always false
+#[cfg(feature = "debug-tools")]
+pub(super) type GodMode<'s> = Read<'s, DebugTools>;
+
+#[cfg(not(feature = "debug-tools"))]
+pub(super) type GodMode<'s> = ();
+
+#[cfg(feature = "debug-tools")]
+pub(super) fn god_mode_enabled(debug_tools: &GodMode<'_>) -> bool {
+    debug_tools.god_mode
+}
+
+#[cfg(not(feature = "debug-tools"))]
+pub(super) fn god_mode_enabled(_debug_tools: &GodMode<'_>) -> bool {
+    false
+}
+
 // big TODO: as this system gets more complicated, at some point it'll probably
 // be worth using ncollide's broad phase collision, which would let us consolidate
 // this and collision.rs.
@@ -17,19 +53,43 @@ use log::info;
 pub struct AttackedSystem;
 
 impl<'s> System<'s> for AttackedSystem {
+    #[allow(clippy::type_complexity)]
     type SystemData = (
         ReadStorage<'s, Transform>,
         WriteStorage<'s, Player>,
+        ReadStorage<'s, PlayerIndex>,
         WriteStorage<'s, Enemy>,
         ReadStorage<'s, Collider>,
         Entities<'s>,
+        Write<'s, PlayerStats>,
+        GodMode<'s>,
+        Write<'s, SoundQueue>,
+        Write<'s, ScreenShake>,
     );
 
     // we don't need `player` here, though if we add health it'd be useful. keeping for now
     // until deciding
-    fn run(&mut self, (transforms, players, enemies, colliders, entities): Self::SystemData) {
-        for (player_entity, _player, player_transform, player_collider) in
-            (&entities, &players, &transforms, &colliders).join()
+    fn run(
+        &mut self,
+        (
+            transforms,
+            players,
+            player_indexes,
+            enemies,
+            colliders,
+            entities,
+            mut player_stats,
+            god_mode,
+            mut sounds,
+            mut shake,
+        ): Self::SystemData,
+    ) {
+        if god_mode_enabled(&god_mode) {
+            return;
+        }
+
+        for (player_entity, _player, player_index, player_transform, player_collider) in
+            (&entities, &players, (&player_indexes).maybe(), &transforms, &colliders).join()
         {
             let player_aabb = player_collider
                 .aabb_from_coordinates(player_transform.translation().x, player_transform.translation().y);
@@ -46,6 +106,9 @@ impl<'s> System<'s> for AttackedSystem {
                 if collides {
                     // this should be a call to some enemy method for reducing health
                     entities.delete(player_entity).unwrap();
+                    player_stats.kill(player_index.map_or(0, |i| i.0));
+                    sounds.play(SoundEffect::PlayerHit);
+                    shake.add(PLAYER_DEATH_TRAUMA);
                 }
             }
         }
@@ -56,19 +119,49 @@ impl<'s> System<'s> for AttackedSystem {
 pub struct ProjectileHitSystem;
 
 impl<'s> System<'s> for ProjectileHitSystem {
+    #[allow(clippy::type_complexity)]
     type SystemData = (
         ReadStorage<'s, Transform>,
         WriteStorage<'s, Player>,
+        ReadStorage<'s, PlayerIndex>,
         WriteStorage<'s, Projectile>,
         ReadStorage<'s, Collider>,
         Entities<'s>,
+        Write<'s, PlayerStats>,
+        Read<'s, PlayableArea>,
+        GodMode<'s>,
+        WriteStorage<'s, Movement>,
+        WriteStorage<'s, Hidden>,
+        Write<'s, Pool<Projectile>>,
+        Write<'s, SoundQueue>,
+        Write<'s, ScreenShake>,
     );
 
     // we don't need `player` here, though if we add health it'd be useful. keeping for now
     // until deciding
-    fn run(&mut self, (transforms, players, projectiles, colliders, entities): Self::SystemData) {
-        for (player_entity, _player, player_transform, player_collider) in
-            (&entities, &players, &transforms, &colliders).join()
+    fn run(
+        &mut self,
+        (
+            transforms,
+            players,
+            player_indexes,
+            mut projectiles,
+            colliders,
+            entities,
+            mut player_stats,
+            playable_area,
+            god_mode,
+            mut movements,
+            mut hiddens,
+            mut projectile_pool,
+            mut sounds,
+            mut shake,
+        ): Self::SystemData,
+    ) {
+        let mut spent = Vec::new();
+
+        for (player_entity, _player, player_index, player_transform, player_collider) in
+            (&entities, &players, (&player_indexes).maybe(), &transforms, &colliders).join()
         {
             let player_aabb = player_collider
                 .aabb_from_coordinates(player_transform.translation().x, player_transform.translation().y);
@@ -86,17 +179,32 @@ impl<'s> System<'s> for ProjectileHitSystem {
                     // we probably don't actually want to delete the player instantly,
                     // but how else will we artificially inflate difficulty in a short game
                     info!("player was hit!");
-                    entities.delete(player_entity).unwrap();
+                    if !god_mode_enabled(&god_mode) {
+                        sounds.play(SoundEffect::PlayerHit);
+                        entities.delete(player_entity).unwrap();
+                        player_stats.kill(player_index.map_or(0, |i| i.0));
+                        shake.add(PLAYER_DEATH_TRAUMA);
+                    }
 
                     // the projectile for sure is no longer needed after contact
-                    entities.delete(projectile_entity).unwrap();
+                    spent.push(projectile_entity);
                 }
 
                 let trans = projectile_transform.translation();
-                if trans.x < 0.0 || trans.x > 2500.0 || trans.y < 0.0 || trans.y > 2500.0 {
-                    entities.delete(projectile_entity).unwrap();
+                if !playable_area.contains(trans.x, trans.y, SHOT_CULL_MARGIN) {
+                    spent.push(projectile_entity);
                 }
             }
         }
+
+        for projectile_entity in spent {
+            recycle_projectile(
+                projectile_entity,
+                &mut projectiles,
+                &mut movements,
+                &mut hiddens,
+                &mut projectile_pool,
+            );
+        }
     }
 }
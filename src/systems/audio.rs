@@ -0,0 +1,87 @@
+use amethyst::{
+    assets::{AssetStorage, Processor},
+    audio::{
+        output::{default_output, Output},
+        AudioSystemDesc, DjSystem, Source,
+    },
+    core::{SystemBundle, SystemDesc},
+    derive::SystemDesc,
+    ecs::{DispatcherBuilder, Read, System, SystemData, World, Write},
+    Error,
+};
+
+use crate::resources::audio::{AudioConfig, Music, SoundHandles, SoundQueue};
+
+// the audio systems, used instead of amethyst's `AudioBundle` since that always
+// opens the default output device. with a null output (or no device at all)
+// sounds are still loaded, but there's nothing to play them on and no `Output`
+// or `AudioSink` is ever created
+pub struct SoundBundle {
+    pub null_output: bool,
+}
+
+impl<'a, 'b> SystemBundle<'a, 'b> for SoundBundle {
+    fn build(self, world: &mut World, builder: &mut DispatcherBuilder<'a, 'b>) -> Result<(), Error> {
+        builder.add(Processor::<Source>::new(), "source_processor", &[]);
+
+        let output = if self.null_output { None } else { default_output() };
+
+        if let Some(output) = output {
+            builder.add(AudioSystemDesc::new(output).build(world), "audio_system", &[]);
+            // `DjSystemDesc` would look for the default device a second time, so
+            // the music system is made directly. `play_music` creates its sink
+            builder.add(DjSystem::new(|music: &mut Music| music.next_track()), "dj_system", &[]);
+        }
+
+        builder.add(SoundSystem, "sound_system", &[]);
+        Ok(())
+    }
+}
+
+// plays the sound effects queued this frame. the queue is emptied even when
+// there's nothing to play them on, so requests don't pile up
+#[derive(SystemDesc)]
+pub struct SoundSystem;
+
+impl<'s> System<'s> for SoundSystem {
+    type SystemData = (
+        Write<'s, SoundQueue>,
+        Option<Read<'s, SoundHandles>>,
+        Option<Read<'s, Output>>,
+        Read<'s, AssetStorage<Source>>,
+        Read<'s, AudioConfig>,
+    );
+
+    fn run(&mut self, (mut queue, handles, output, sources, audio_config): Self::SystemData) {
+        let requests = queue.drain();
+
+        if let (Some(handles), Some(output)) = (handles, output) {
+            for effect in requests {
+                if let Some(sound) = sources.get(handles.get(effect)) {
+                    output.play_once(sound, audio_config.sfx_level());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use amethyst::{audio::AudioSink, ecs::WorldExt};
+
+    #[test]
+    fn null_output_opens_no_device() {
+        let mut world = World::new();
+        world.insert(Music::default());
+
+        let mut builder = DispatcherBuilder::new();
+        SoundBundle { null_output: true }.build(&mut world, &mut builder).unwrap();
+        let mut dispatcher = builder.build();
+        dispatcher.setup(&mut world);
+
+        assert!(!world.has_value::<Output>());
+        assert!(!world.has_value::<AudioSink>());
+    }
+}
@@ -0,0 +1,119 @@
+use amethyst::{
+    core::Transform,
+    derive::SystemDesc,
+    ecs::{Join, ReadStorage, System, SystemData, Write, WriteStorage},
+    renderer::SpriteRender,
+    ui::UiTransform,
+};
+
+use crate::{
+    components::{animation::Animation, boss::Boss, hud::BossHealthBar, launcher::Launcher, movement::Movement},
+    entities::enemy::Enemy,
+    resources::{
+        particles::{BurstKind, ParticleBursts},
+        shake::{ScreenShake, BOSS_PHASE_TRAUMA},
+    },
+};
+
+use log::info;
+
+// switches boss phases based on health. each phase replaces the boss's
+// movement strategy, speed, fire pattern and (optionally) sprite or animations
+#[derive(SystemDesc)]
+pub struct BossSystem;
+
+impl<'s> System<'s> for BossSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        WriteStorage<'s, Boss>,
+        ReadStorage<'s, Enemy>,
+        WriteStorage<'s, Movement>,
+        WriteStorage<'s, Launcher>,
+        WriteStorage<'s, SpriteRender>,
+        ReadStorage<'s, Transform>,
+        Write<'s, ParticleBursts>,
+        WriteStorage<'s, Animation>,
+        Write<'s, ScreenShake>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut bosses,
+            enemies,
+            mut movements,
+            mut launchers,
+            mut sprites,
+            transforms,
+            mut bursts,
+            mut animations,
+            mut shake,
+        ): Self::SystemData,
+    ) {
+        for (boss, enemy, movement, launcher, sprite, transform, animation) in (
+            &mut bosses,
+            &enemies,
+            &mut movements,
+            &mut launchers,
+            &mut sprites,
+            &transforms,
+            (&mut animations).maybe(),
+        )
+            .join()
+        {
+            // the first phase starts quietly, later ones get a burst
+            let started = boss.has_started();
+
+            if let Some(phase) = boss.next_phase(enemy.health) {
+                info!("boss entering phase: {:?}", phase);
+
+                if started {
+                    bursts.request(BurstKind::BossPhase, transform.translation().x, transform.translation().y);
+                    shake.add(BOSS_PHASE_TRAUMA);
+                }
+
+                // clear any locked direction from the previous movement strategy
+                movement.movement_type = phase.movement_type;
+                movement.speed = phase.speed;
+                movement.freeze_direction = false;
+                movement.locked_direction = None;
+                movement.already_rotated = false;
+
+                launcher.pattern = phase.pattern;
+
+                if let Some(sprite_number) = phase.sprite_number {
+                    sprite.sprite_number = sprite_number;
+                }
+
+                if let Some(animation) = animation {
+                    animation.replace_clips(&phase.clips);
+                }
+            }
+        }
+    }
+}
+
+// scales the boss health bar in the HUD. the bar empties once the boss is gone
+#[derive(SystemDesc)]
+pub struct BossHealthBarSystem;
+
+impl<'s> System<'s> for BossHealthBarSystem {
+    type SystemData = (
+        ReadStorage<'s, Boss>,
+        ReadStorage<'s, Enemy>,
+        ReadStorage<'s, BossHealthBar>,
+        WriteStorage<'s, UiTransform>,
+    );
+
+    fn run(&mut self, (bosses, enemies, health_bars, mut ui_transforms): Self::SystemData) {
+        let fraction = (&bosses, &enemies)
+            .join()
+            .next()
+            .map(|(boss, enemy)| boss.health_fraction(enemy.health))
+            .unwrap_or(0.0);
+
+        for (health_bar, ui_transform) in (&health_bars, &mut ui_transforms).join() {
+            ui_transform.width = health_bar.full_width * fraction;
+        }
+    }
+}
@@ -1,15 +1,30 @@
-use nalgebra::{Isometry2, Vector2};
-use ncollide2d::{bounding_volume, shape::Cuboid};
-
 use amethyst::{
-    core::Transform,
+    core::{Hidden, Transform},
     derive::SystemDesc,
-    ecs::{Entities, Join, ReadStorage, System, SystemData, WriteStorage},
+    ecs::{Entities, Join, ReadStorage, System, SystemData, Write, WriteStorage},
+    renderer::resources::Tint,
 };
 
 use crate::{
-    components::collider::Collider,
-    entities::{enemy::Enemy, laser::Laser},
+    components::{
+        animation::{Animation, AnimationId},
+        boss::Boss,
+        collider::Collider,
+        effects::{flash, HitFlash},
+        launcher::Launcher,
+        movement::Movement,
+    },
+    entities::{
+        enemy::Enemy,
+        laser::{Laser, LASER_COLLIDER},
+    },
+    resources::{
+        audio::{SoundEffect, SoundQueue},
+        level::LevelProgress,
+        particles::{BurstKind, ParticleBursts},
+        players::PlayerStats,
+        pool::Pool,
+    },
 };
 
 use log::info;
@@ -26,32 +41,50 @@ impl<'s> System<'s> for CollisionSystem {
         WriteStorage<'s, Laser>,
         WriteStorage<'s, Enemy>,
         Entities<'s>,
-        ReadStorage<'s, Collider>,
+        WriteStorage<'s, Collider>,
+        Write<'s, PlayerStats>,
+        WriteStorage<'s, Animation>,
+        WriteStorage<'s, Launcher>,
+        WriteStorage<'s, Movement>,
+        WriteStorage<'s, HitFlash>,
+        WriteStorage<'s, Tint>,
+        Write<'s, ParticleBursts>,
+        WriteStorage<'s, Hidden>,
+        Write<'s, Pool<Laser>>,
+        Write<'s, SoundQueue>,
+        ReadStorage<'s, Boss>,
+        Write<'s, LevelProgress>,
     );
 
-    fn run(&mut self, (transforms, lasers, mut enemies, entities, colliders): Self::SystemData) {
-        for (laser_entity, _laser_a, transform_a) in (&entities, &lasers, &transforms).join() {
-            /*
-             * Initialize the shapes.
-             */
-            // this is for a laser much larger than ours. agh.
-            // the x, y should be the half length along the x and y axes, respectively
-            // for a ball type you'd use a radius instead. this creates a representation of
-            // the shape and a size of the shape, but *not* positioning of any kind
-            // this number should be in a config somewhere... it's the pixel width 7 and height 1,
-            // both scaled by 5, and then divided in two to get the half length
-            let laser_cube = Cuboid::new(Vector2::new(17.5, 2.5));
-
-            // next we need to create an isometry representation of the position, which for 2d
-            // ncollide is a vector of the x and y coordinates and a rotation (zero() for no rotation).
-            // the actual rotation is available via some_transform.isometry(), but
-            let laser_cube_pos = Isometry2::new(
-                Vector2::new(transform_a.translation().x, transform_a.translation().y),
-                nalgebra::zero(),
-            );
+    fn run(
+        &mut self,
+        (
+            transforms,
+            mut lasers,
+            mut enemies,
+            entities,
+            mut colliders,
+            mut player_stats,
+            mut animations,
+            mut launchers,
+            mut movements,
+            mut flashes,
+            tints,
+            mut bursts,
+            mut hiddens,
+            mut laser_pool,
+            mut sounds,
+            bosses,
+            mut level_progress,
+        ): Self::SystemData,
+    ) {
+        let mut dying = Vec::new();
+        let mut spent = Vec::new();
 
-            // a bounding volume is the combination of a shape and a position
-            let aabb_laser = bounding_volume::aabb(&laser_cube, &laser_cube_pos);
+        for (laser_entity, laser, transform_a) in (&entities, &lasers, &transforms).join() {
+            // the laser collider assumes no rotation, same as every other collider
+            let aabb_laser =
+                LASER_COLLIDER.aabb_from_coordinates(transform_a.translation().x, transform_a.translation().y);
 
             for (enemy_entity, enemy, enemy_transform, enemy_collider) in
                 (&entities, &mut enemies, &transforms, &colliders).join()
@@ -65,16 +98,58 @@ impl<'s> System<'s> for CollisionSystem {
                 // possible if more than one laser hits in a frame
                 if collides && !enemy.is_dead() {
                     enemy.take_damage(20.0);
-                    // we should probably destroy the laser too
-                    entities.delete(laser_entity).unwrap();
+                    if let Some(animation) = animations.get_mut(enemy_entity) {
+                        animation.restart(AnimationId::Hit);
+                    }
+                    // the laser goes back to the pool once all the hits are worked out
+                    spent.push(laser_entity);
+                    bursts.request(
+                        BurstKind::LaserImpact,
+                        transform_a.translation().x,
+                        transform_a.translation().y,
+                    );
 
-                    // if the enemy has taken enough damage, delete them
+                    // if the enemy has taken enough damage, it plays its death
+                    // animation (`AnimationCleanupSystem` deletes it once that's
+                    // done), or is deleted right away if it doesn't have one
                     // TODO: may be a latent bug in associating this with laser hits...
-                    if enemy.is_dead() && entities.delete(enemy_entity).is_ok() {
-                        info!("enemy deleted due to insufficient laser dodging abilities");
+                    if enemy.is_dead() {
+                        info!("enemy killed due to insufficient laser dodging abilities");
+                        player_stats.add_score(laser.owner, enemy.points);
+                        bursts.request(BurstKind::Explosion, x, y);
+                        sounds.play(SoundEffect::EnemyDeath);
+
+                        if bosses.contains(enemy_entity) {
+                            level_progress.boss_defeated = true;
+                        }
+
+                        match animations.get_mut(enemy_entity) {
+                            Some(animation) if animation.has_clip(AnimationId::Death) => {
+                                animation.restart(AnimationId::Death);
+                                dying.push(enemy_entity);
+                            },
+                            _ => {
+                                let _ = entities.delete(enemy_entity);
+                            },
+                        }
+                    } else {
+                        flash(enemy_entity, &mut flashes, &tints);
+                        sounds.play(SoundEffect::EnemyHit);
                     }
                 }
             }
         }
+
+        for laser_entity in spent {
+            laser_pool.recycle(laser_entity, &mut lasers, &mut hiddens);
+        }
+
+        // dying enemies stay on screen for their death animation, but stop
+        // moving, firing and touching anything
+        for enemy_entity in dying {
+            colliders.remove(enemy_entity);
+            launchers.remove(enemy_entity);
+            movements.remove(enemy_entity);
+        }
     }
 }
@@ -0,0 +1,22 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::{Join, Read, System, SystemData, WriteStorage},
+};
+
+use crate::components::cooldown::Cooldown;
+
+// ticks every weapon cooldown once per frame. this used to happen inside
+// `can_fire`, which meant time only counted while the fire input was held
+#[derive(SystemDesc)]
+pub struct CooldownSystem;
+
+impl<'s> System<'s> for CooldownSystem {
+    type SystemData = (WriteStorage<'s, Cooldown>, Read<'s, Time>);
+
+    fn run(&mut self, (mut cooldowns, time): Self::SystemData) {
+        for cooldown in (&mut cooldowns).join() {
+            cooldown.tick(time.delta_seconds());
+        }
+    }
+}
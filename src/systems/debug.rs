@@ -0,0 +1,90 @@
+use amethyst::{
+    core::{math::Point2, Hidden, Transform},
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write},
+    renderer::{debug_drawing::DebugLines, palette::Srgba},
+};
+
+use crate::{
+    components::collider::Collider,
+    entities::laser::{Laser, LASER_COLLIDER},
+    resources::{
+        debug::DebugTools,
+        playablearea::{PlayableArea, MOVER_CULL_MARGIN, SHOT_CULL_MARGIN},
+    },
+};
+
+use ncollide2d::bounding_volume::AABB;
+
+// draw everything slightly above the sprites so the lines aren't hidden
+const OVERLAY_Z: f32 = 10.0;
+
+// only compiled in with the `debug-tools` feature. when the overlay is toggled
+// on, this draws every collider AABB and the arena limits so hitboxes can be
+// checked visually instead of guessing at prefab values. pooled projectiles
+// keep their colliders while they're hidden, so hidden entities are skipped
+#[derive(SystemDesc)]
+pub struct DebugOverlaySystem;
+
+impl<'s> System<'s> for DebugOverlaySystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Collider>,
+        ReadStorage<'s, Laser>,
+        ReadStorage<'s, Hidden>,
+        Read<'s, PlayableArea>,
+        Read<'s, DebugTools>,
+        Write<'s, DebugLines>,
+    );
+
+    fn run(
+        &mut self,
+        (transforms, colliders, lasers, hiddens, playable_area, debug_tools, mut debug_lines): Self::SystemData,
+    ) {
+        if !debug_tools.show_overlay {
+            return;
+        }
+
+        let collider_color = Srgba::new(0.0, 1.0, 0.0, 1.0);
+        let laser_color = Srgba::new(1.0, 1.0, 0.0, 1.0);
+        let area_color = Srgba::new(0.0, 0.5, 1.0, 1.0);
+        let bounds_color = Srgba::new(1.0, 0.0, 0.0, 1.0);
+
+        for (collider, transform, _) in (&colliders, &transforms, !&hiddens).join() {
+            let aabb = collider.aabb_from_coordinates(transform.translation().x, transform.translation().y);
+            draw_aabb(&mut debug_lines, &aabb, collider_color);
+        }
+
+        for (_laser, transform) in (&lasers, &transforms).join() {
+            let aabb = LASER_COLLIDER.aabb_from_coordinates(transform.translation().x, transform.translation().y);
+            draw_aabb(&mut debug_lines, &aabb, laser_color);
+        }
+
+        let (min_x, max_x, min_y, max_y) = playable_area.bounds();
+        debug_lines.draw_rectangle(
+            Point2::new(min_x, min_y),
+            Point2::new(max_x, max_y),
+            OVERLAY_Z,
+            area_color,
+        );
+
+        // the culling limits used by `TransformUpdateSystem` (movers) and
+        // `LaserSystem`/`ProjectileHitSystem` (lasers and projectiles)
+        for margin in [MOVER_CULL_MARGIN, SHOT_CULL_MARGIN].iter() {
+            let (min_x, max_x, min_y, max_y) = playable_area.bounds_with_margin(*margin);
+            debug_lines.draw_rectangle(Point2::new(min_x, min_y), Point2::new(max_x, max_y), OVERLAY_Z, bounds_color);
+        }
+    }
+}
+
+// the ncollide and amethyst points come from different nalgebra versions, so
+// the coordinates have to be copied over by hand
+fn draw_aabb(debug_lines: &mut DebugLines, aabb: &AABB<f32>, color: Srgba) {
+    debug_lines.draw_rectangle(
+        Point2::new(aabb.mins().x, aabb.mins().y),
+        Point2::new(aabb.maxs().x, aabb.maxs().y),
+        OVERLAY_Z,
+        color,
+    );
+}
@@ -0,0 +1,48 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, System, SystemData, WriteStorage},
+    renderer::resources::Tint,
+};
+
+use crate::components::effects::HitFlash;
+
+// steps damage flashes, and removes them when they're done
+#[derive(SystemDesc)]
+pub struct EffectsSystem;
+
+impl<'s> System<'s> for EffectsSystem {
+    type SystemData = (
+        WriteStorage<'s, HitFlash>,
+        WriteStorage<'s, Tint>,
+        Read<'s, Time>,
+        Entities<'s>,
+    );
+
+    fn run(&mut self, (mut flashes, mut tints, time, entities): Self::SystemData) {
+        let time_delta = time.delta_seconds();
+
+        let mut finished = Vec::new();
+        for (entity, flash) in (&entities, &mut flashes).join() {
+            match flash.next_tint(time_delta) {
+                Some(tint) => {
+                    tints.insert(entity, tint).expect("unable to flash entity");
+                },
+                None => finished.push((entity, flash.previous())),
+            }
+        }
+
+        // put back whatever tint the entity had before it was hit
+        for (entity, previous) in finished {
+            flashes.remove(entity);
+            match previous {
+                Some(tint) => {
+                    tints.insert(entity, tint).expect("unable to restore tint");
+                },
+                None => {
+                    tints.remove(entity);
+                },
+            }
+        }
+    }
+}
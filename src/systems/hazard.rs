@@ -0,0 +1,129 @@
+use amethyst::{
+    core::{timing::Time, Transform},
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+};
+
+use crate::{
+    components::{
+        collider::Collider,
+        hazard::{DamageZone, SlowZone, Teleporter},
+    },
+    entities::player::{Player, PlayerIndex},
+    resources::{
+        audio::{SoundEffect, SoundQueue},
+        players::PlayerStats,
+        shake::{ScreenShake, PLAYER_DEATH_TRAUMA},
+    },
+};
+
+use super::attacked::{god_mode_enabled, GodMode};
+
+use log::info;
+
+// applies hazard tiles to the players standing on them. this runs before
+// `PlayerSystem` so slow zones affect movement in the same frame
+#[derive(SystemDesc)]
+pub struct HazardSystem;
+
+impl<'s> System<'s> for HazardSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Player>,
+        ReadStorage<'s, PlayerIndex>,
+        ReadStorage<'s, Collider>,
+        ReadStorage<'s, DamageZone>,
+        ReadStorage<'s, SlowZone>,
+        ReadStorage<'s, Teleporter>,
+        Write<'s, PlayerStats>,
+        Read<'s, Time>,
+        Entities<'s>,
+        GodMode<'s>,
+        Write<'s, SoundQueue>,
+        Write<'s, ScreenShake>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut transforms,
+            mut players,
+            player_indexes,
+            colliders,
+            damage_zones,
+            slow_zones,
+            teleporters,
+            mut player_stats,
+            time,
+            entities,
+            god_mode,
+            mut sounds,
+            mut shake,
+        ): Self::SystemData,
+    ) {
+        // hazards never move, so their positions can be copied out before the
+        // player transforms are borrowed mutably
+        let mut damage = Vec::new();
+        for (zone, collider, transform) in (&damage_zones, &colliders, &transforms).join() {
+            let aabb = collider.aabb_from_coordinates(transform.translation().x, transform.translation().y);
+            damage.push((aabb, zone.damage_per_second));
+        }
+
+        let mut slow = Vec::new();
+        for (zone, collider, transform) in (&slow_zones, &colliders, &transforms).join() {
+            let aabb = collider.aabb_from_coordinates(transform.translation().x, transform.translation().y);
+            slow.push((aabb, zone.speed_scale));
+        }
+
+        let mut pads = Vec::new();
+        for (pad, collider, transform) in (&teleporters, &colliders, &transforms).join() {
+            let aabb = collider.aabb_from_coordinates(transform.translation().x, transform.translation().y);
+            pads.push((aabb, *pad));
+        }
+
+        for (entity, player, player_index, collider, transform) in
+            (&entities, &mut players, (&player_indexes).maybe(), &colliders, &mut transforms).join()
+        {
+            let (x, y) = (transform.translation().x, transform.translation().y);
+            let index = player_index.map_or(0, |i| i.0);
+
+            let damage_per_second: f32 = damage
+                .iter()
+                .filter(|(aabb, _)| collider.intersects(x, y, aabb))
+                .map(|(_, damage_per_second)| damage_per_second)
+                .sum();
+
+            if damage_per_second > 0.0 && !god_mode_enabled(&god_mode) {
+                player.health -= damage_per_second * time.delta_seconds();
+
+                if player.health <= 0.0 {
+                    info!("player {} was electrocuted", index + 1);
+                    entities.delete(entity).unwrap();
+                    player_stats.kill(index);
+                    sounds.play(SoundEffect::PlayerHit);
+                    shake.add(PLAYER_DEATH_TRAUMA);
+                    continue;
+                }
+            }
+
+            player.speed_scale = slow
+                .iter()
+                .filter(|(aabb, _)| collider.intersects(x, y, aabb))
+                .map(|(_, speed_scale)| *speed_scale)
+                .fold(1.0, f32::min);
+
+            let pad = pads.iter().find(|(aabb, _)| collider.intersects(x, y, aabb));
+
+            match pad {
+                Some((_, pad)) if !player.on_teleporter => {
+                    transform.set_translation_x(pad.destination_x);
+                    transform.set_translation_y(pad.destination_y);
+                    player.on_teleporter = true;
+                },
+                Some(_) => {},
+                None => player.on_teleporter = false,
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use amethyst::{
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, WriteStorage},
+    ui::UiText,
+};
+
+use crate::{components::hud::PlayerHud, resources::players::PlayerStats};
+
+// keeps each player's lives and score text in sync with `PlayerStats`
+#[derive(SystemDesc)]
+pub struct PlayerHudSystem;
+
+impl<'s> System<'s> for PlayerHudSystem {
+    type SystemData = (
+        ReadStorage<'s, PlayerHud>,
+        WriteStorage<'s, UiText>,
+        Read<'s, PlayerStats>,
+    );
+
+    fn run(&mut self, (huds, mut texts, player_stats): Self::SystemData) {
+        for (hud, text) in (&huds, &mut texts).join() {
+            if let Some(record) = player_stats.get(hud.index) {
+                let status = if record.is_out() {
+                    format!("P{}  Out  Score: {}", hud.index + 1, record.score)
+                } else {
+                    format!("P{}  Lives: {}  Score: {}", hud.index + 1, record.lives, record.score)
+                };
+
+                // only touch the text when it changes so the glyphs aren't rebuilt every frame
+                if text.text != status {
+                    text.text = status;
+                }
+            }
+        }
+    }
+}
@@ -1,79 +1,57 @@
 use amethyst::{
-    core::{timing::Time, Transform},
+    core::{timing::Time, Hidden, Transform},
     derive::SystemDesc,
-    ecs::{Entities, Join, Read, System, SystemData, WriteStorage},
+    ecs::{Entities, Join, Read, System, SystemData, Write, WriteStorage},
 };
 
-use crate::entities::laser::{Direction::*, Laser};
-use log::info;
+use crate::{
+    entities::laser::Laser,
+    resources::{
+        playablearea::{PlayableArea, SHOT_CULL_MARGIN},
+        pool::Pool,
+    },
+};
 
 // this system is concerned only with lasers that have already been spawned.
 // the entity exists but the transform needs to be continuously updated based
-// on the direction.
-// if it collides with a border it should also be destroyed.
+// on the direction vector.
+// once it's far enough outside the arena it goes back to the laser pool.
 #[derive(SystemDesc)]
 pub struct LaserSystem;
 
 impl<'s> System<'s> for LaserSystem {
+    #[allow(clippy::type_complexity)]
     type SystemData = (
         WriteStorage<'s, Transform>,
         WriteStorage<'s, Laser>,
         Entities<'s>,
         Read<'s, Time>,
+        Read<'s, PlayableArea>,
+        WriteStorage<'s, Hidden>,
+        Write<'s, Pool<Laser>>,
     );
 
-    fn run(&mut self, (mut transforms, lasers, entities, time): Self::SystemData) {
+    fn run(
+        &mut self,
+        (mut transforms, mut lasers, entities, time, playable_area, mut hiddens, mut laser_pool): Self::SystemData,
+    ) {
+        let mut expired = Vec::new();
+
         for (entity, laser, transform) in (&entities, &lasers, &mut transforms).join() {
             // constant laser speed.. still shouldn't be hardcoded though.
+            let velocity = laser.velocity() * time.delta_seconds();
+            transform.prepend_translation_x(velocity.x);
+            transform.prepend_translation_y(velocity.y);
             let &trans = transform.translation();
-            let neg_x = trans.x - laser.speed * time.delta_seconds();
-            let neg_y = trans.y - laser.speed * time.delta_seconds();
-            let pos_x = trans.x + laser.speed * time.delta_seconds();
-            let pos_y = trans.y + laser.speed * time.delta_seconds();
 
-            // probably no reason to compute this every frame for every laser
-            // it'd be easier to have the laser track `.next_change` or something
-            // similar
-            match &laser.direction {
-                Left => {
-                    transform.set_translation_x(neg_x);
-                },
-                Right => {
-                    transform.set_translation_x(pos_x);
-                },
-                Up => {
-                    transform.set_translation_y(pos_y);
-                },
-                Down => {
-                    transform.set_translation_y(neg_y);
-                },
-                RightUp => {
-                    transform.set_translation_x(pos_x);
-                    transform.set_translation_y(pos_y);
-                },
-                LeftUp => {
-                    transform.set_translation_x(neg_x);
-                    transform.set_translation_y(pos_y);
-                },
-                LeftDown => {
-                    transform.set_translation_x(neg_x);
-                    transform.set_translation_y(neg_y);
-                },
-                RightDown => {
-                    transform.set_translation_x(pos_x);
-                    transform.set_translation_y(neg_y);
-                },
+            // recycle lasers once they're far enough outside the arena
+            if !playable_area.contains(trans.x, trans.y, SHOT_CULL_MARGIN) {
+                expired.push(entity);
             }
+        }
 
-            // this will change when we add rudimentary collision detection. for now
-            // it's just a bounds check that'll delete lasers once they go off screen.
-            if trans.x < 0.0 || trans.x > 2500.0 || trans.y < 0.0 || trans.y > 2500.0 {
-                let deleted = entities.delete(entity);
-
-                if let Err(msg) = deleted {
-                    info!("A terrible error has occured: {:?}", msg)
-                }
-            }
+        for entity in expired {
+            laser_pool.recycle(entity, &mut lasers, &mut hiddens);
         }
     }
 }
@@ -1,17 +1,45 @@
+#[cfg(feature = "debug-tools")]
+pub use self::debug::DebugOverlaySystem;
 pub use self::{
+    ai::AiSystem,
+    animation::{AnimationCleanupSystem, AnimationSystem},
     attacked::{AttackedSystem, ProjectileHitSystem},
+    audio::SoundBundle,
+    boss::{BossHealthBarSystem, BossSystem},
     collision::CollisionSystem,
+    cooldown::CooldownSystem,
+    effects::EffectsSystem,
     fade::FadeSystem,
+    hazard::HazardSystem,
+    hud::PlayerHudSystem,
     laser::LaserSystem,
     movement::{MovementTrackingSystem, TransformUpdateSystem},
+    particles::ParticleSystem,
     player::PlayerSystem,
     projectiles::ProjectilesSystem,
+    shake::ScreenShakeSystem,
+    steering::SteeringSystem,
+    walls::WallCollisionSystem,
 };
 
+mod ai;
+mod animation;
 mod attacked;
+mod audio;
+mod boss;
 mod collision;
+mod cooldown;
+#[cfg(feature = "debug-tools")]
+mod debug;
+mod effects;
 mod fade;
+mod hazard;
+mod hud;
 mod laser;
 mod movement;
+mod particles;
 mod player;
 mod projectiles;
+mod shake;
+mod steering;
+mod walls;
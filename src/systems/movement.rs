@@ -1,46 +1,91 @@
 use amethyst::{
-    core::{timing::Time, Transform},
+    core::{timing::Time, Hidden, Transform},
     derive::SystemDesc,
-    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, WriteStorage},
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
 };
 
-use crate::{components::movement::Movement, entities::player::Player};
+use crate::{
+    components::{
+        launcher::{recycle_projectile, Projectile},
+        movement::{Movement, MovementType},
+        spawnpoint::SpawnPoint,
+        targeting::{player_positions, Targeting},
+    },
+    entities::player::{Player, PlayerIndex},
+    resources::{
+        flowfield::FlowFields,
+        playablearea::{PlayableArea, MOVER_CULL_MARGIN},
+        pool::Pool,
+        walls::Walls,
+    },
+};
 
 use std::f32::consts::PI;
 
 use log::info;
 
+// each mover chases the player picked by its `Targeting` (the nearest player
+// by default). with no players left, movers keep their last velocity.
+// `Gravitate` movers follow the flow fields around walls instead of heading
+// straight for the player when something is in the way
 #[derive(SystemDesc)]
 pub struct MovementTrackingSystem;
 
 impl<'s> System<'s> for MovementTrackingSystem {
+    #[allow(clippy::type_complexity)]
     type SystemData = (
         ReadStorage<'s, Transform>,
         WriteStorage<'s, Movement>,
+        ReadStorage<'s, Targeting>,
         ReadStorage<'s, Player>,
+        ReadStorage<'s, PlayerIndex>,
+        Read<'s, Time>,
+        Read<'s, PlayableArea>,
+        Read<'s, Walls>,
+        Write<'s, FlowFields>,
     );
 
-    fn run(&mut self, (transforms, mut movements, players): Self::SystemData) {
-        for (movement, transform) in (&mut movements, &transforms).join() {
-            for (_player, player_transform) in (&players, &transforms).join() {
+    fn run(
+        &mut self,
+        (
+            transforms,
+            mut movements,
+            targetings,
+            players,
+            player_indexes,
+            time,
+            playable_area,
+            walls,
+            mut flow_fields,
+        ): Self::SystemData,
+    ) {
+        let player_positions = player_positions(&players, &player_indexes, &transforms);
+        flow_fields.update(&player_positions, &walls);
+
+        for (movement, transform, targeting) in (&mut movements, &transforms, (&targetings).maybe()).join() {
+            let (x, y) = (transform.translation().x, transform.translation().y);
+            let targeting = targeting.copied().unwrap_or_default();
+
+            if let Some(target) = targeting.pick_target(x, y, &player_positions) {
                 // this updates the x and y velocities on the enemy struct, which
                 // can be used in another system to modify the transform
                 // we can't modify it here because we can't take ownership of mut
-                // transforms in the outer join and still get player transforms in the
-                // inner join
-                movement.next_move(
-                    player_transform.translation().x,
-                    player_transform.translation().y,
-                    player_transform.translation().z,
-                    transform.translation().x,
-                    transform.translation().y,
-                );
+                // transforms and still get player transforms
+                let (goal_x, goal_y) = match movement.movement_type {
+                    MovementType::Gravitate => flow_fields.waypoint(x, y, target.x, target.y, &walls),
+                    _ => (target.x, target.y),
+                };
+
+                movement.next_move(goal_x, goal_y, target.z, x, y, time.delta_seconds(), &playable_area);
             }
         }
     }
 }
 
-// now we can update the transform
+// now we can update the transform. this also keeps movers inside the arena
+// (see `Boundary`) and culls anything that gets too far outside it. culled
+// enemies go back to their spawn point, since deleting them would count as a
+// kill and end the level early. culled projectiles go back to their pool
 #[derive(SystemDesc)]
 pub struct TransformUpdateSystem;
 
@@ -49,26 +94,40 @@ impl<'s> System<'s> for TransformUpdateSystem {
     type SystemData = (
         WriteStorage<'s, Transform>,
         WriteStorage<'s, Movement>,
+        ReadStorage<'s, SpawnPoint>,
         Read<'s, Time>,
+        Read<'s, PlayableArea>,
         Entities<'s>,
+        WriteStorage<'s, Projectile>,
+        WriteStorage<'s, Hidden>,
+        Write<'s, Pool<Projectile>>,
     );
 
-    fn run(&mut self, (mut transforms, mut movements, time, entities): Self::SystemData) {
-        for (movement, enemy_entity, enemy_transform) in (&mut movements, &entities, &mut transforms).join() {
-            enemy_transform.prepend_translation_x(movement.velocity_x * time.delta_seconds());
-            enemy_transform.prepend_translation_y(movement.velocity_y * time.delta_seconds());
-            // TODO: decide if we need to clamp movement for enemies. and if so, perhaps look into why
-            // this code causes the game to speed through levels and exit quickly
-            //let new_x = playable_area.clamp_x(movement.velocity_x * time.delta_seconds());
-            //enemy_transform.prepend_translation_x(new_x);
+    fn run(
+        &mut self,
+        (
+            mut transforms,
+            mut movements,
+            spawn_points,
+            time,
+            playable_area,
+            entities,
+            mut projectiles,
+            mut hiddens,
+            mut projectile_pool,
+        ): Self::SystemData,
+    ) {
+        let mut culled_projectiles = Vec::new();
 
-            //let new_y = playable_area.clamp_y(movement.velocity_y * time.delta_seconds());
-            //enemy_transform.prepend_translation_y(new_y);
-
-            // these values should be based on game dimensions. the check is needed
-            // for enemies that move off screen before getting hit
-            let x = enemy_transform.translation().x;
-            let y = enemy_transform.translation().y;
+        for (movement, enemy_entity, enemy_transform) in (&mut movements, &entities, &mut transforms).join() {
+            let (velocity_x, velocity_y) = movement.steered_velocity();
+            let (x, y) = movement.confine(
+                enemy_transform.translation().x + velocity_x * time.delta_seconds(),
+                enemy_transform.translation().y + velocity_y * time.delta_seconds(),
+                &playable_area,
+            );
+            enemy_transform.set_translation_x(x);
+            enemy_transform.set_translation_y(y);
 
             // maybe TODO: smooth rotation? or for projectiles at least,
             // rotated before being spawned
@@ -87,12 +146,30 @@ impl<'s> System<'s> for TransformUpdateSystem {
                 }
             }
 
-            // TODO: this should be based on some kind of "playable area" dimensions resource
-            let out_of_bounds = x < -500.0 || x > 2500.0 || y < -500.0 || y > 2500.0;
+            if playable_area.contains(x, y, MOVER_CULL_MARGIN) {
+                continue;
+            }
 
-            if out_of_bounds && entities.delete(enemy_entity).is_ok() {
-                info!("enemy out of bounds");
+            if let Some(spawn_point) = spawn_points.get(enemy_entity) {
+                info!("enemy out of bounds, returning to its spawn point");
+                enemy_transform.set_translation_x(spawn_point.x);
+                enemy_transform.set_translation_y(spawn_point.y);
+                movement.reset();
+            } else if projectiles.contains(enemy_entity) {
+                culled_projectiles.push(enemy_entity);
+            } else if entities.delete(enemy_entity).is_ok() {
+                info!("mover out of bounds");
             }
         }
+
+        for projectile_entity in culled_projectiles {
+            recycle_projectile(
+                projectile_entity,
+                &mut projectiles,
+                &mut movements,
+                &mut hiddens,
+                &mut projectile_pool,
+            );
+        }
     }
 }
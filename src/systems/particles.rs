@@ -0,0 +1,138 @@
+use amethyst::{
+    core::{math::Vector3, timing::Time, Hidden, Transform},
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, ReadExpect, System, SystemData, Write, WriteStorage},
+    renderer::{resources::Tint, SpriteRender, Transparent},
+};
+
+use crate::{
+    components::{
+        cleanup::CleanupTag,
+        particles::{Emitter, Particle},
+    },
+    resources::{
+        handles::GameplayHandles,
+        particles::{ParticleBursts, ParticleEffects, BURST_ANGLE},
+        pool::Pool,
+    },
+};
+
+// particles use the 32 pixel tile sprite, scaled to their size
+const TILE_SIZE: f32 = 32.0;
+
+// moves and fades live particles, returns expired ones to the pool, and then
+// creates new ones for this frame's bursts and emitters
+#[derive(SystemDesc)]
+pub struct ParticleSystem;
+
+impl<'s> System<'s> for ParticleSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        WriteStorage<'s, Particle>,
+        WriteStorage<'s, Emitter>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Tint>,
+        WriteStorage<'s, SpriteRender>,
+        WriteStorage<'s, Hidden>,
+        WriteStorage<'s, Transparent>,
+        WriteStorage<'s, CleanupTag>,
+        Write<'s, Pool<Particle>>,
+        Write<'s, ParticleBursts>,
+        ReadExpect<'s, ParticleEffects>,
+        ReadExpect<'s, GameplayHandles>,
+        Read<'s, Time>,
+        Entities<'s>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut particles,
+            mut emitters,
+            mut transforms,
+            mut tints,
+            mut sprites,
+            mut hiddens,
+            mut transparents,
+            mut cleanup_tags,
+            mut pool,
+            mut bursts,
+            effects,
+            handles,
+            time,
+            entities,
+        ): Self::SystemData,
+    ) {
+        let time_delta = time.delta_seconds();
+
+        let mut expired = Vec::new();
+        for (entity, particle, transform, tint) in (&entities, &mut particles, &mut transforms, &mut tints).join() {
+            if !particle.is_active() {
+                continue;
+            }
+
+            match particle.update(time_delta) {
+                Some((dx, dy)) => {
+                    let (color, size) = particle.appearance();
+                    transform.prepend_translation_x(dx);
+                    transform.prepend_translation_y(dy);
+                    transform.set_scale(Vector3::new(size / TILE_SIZE, size / TILE_SIZE, 1.0));
+                    tint.0 = color;
+                },
+                None => expired.push(entity),
+            }
+        }
+
+        for entity in expired {
+            hiddens.insert(entity, Hidden).expect("unable to hide particle");
+            pool.release(entity);
+        }
+
+        // everything to create this frame, with its starting position
+        let mut spawns = Vec::new();
+        for (kind, x, y) in bursts.drain() {
+            let effect = effects.get(kind);
+            for _ in 0 .. effect.count {
+                spawns.push((Particle::new(effect, BURST_ANGLE), x, y));
+            }
+        }
+
+        for (emitter, transform) in (&mut emitters, &transforms).join() {
+            let (x, y) = (transform.translation().x, transform.translation().y);
+            if let Some((count, angle)) = emitter.update(x, y, time_delta) {
+                for _ in 0 .. count {
+                    spawns.push((Particle::new(&emitter.effect, angle), x, y));
+                }
+            }
+        }
+
+        for (particle, x, y) in spawns {
+            // past the cap the rest of this frame's particles are skipped
+            let (entity, reused) = match pool.acquire(&entities) {
+                Some(acquired) => acquired,
+                None => break,
+            };
+
+            let (color, size) = particle.appearance();
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(x, y, 0.0);
+            transform.set_scale(Vector3::new(size / TILE_SIZE, size / TILE_SIZE, 1.0));
+
+            if reused {
+                hiddens.remove(entity);
+            } else {
+                let sprite_render = SpriteRender {
+                    sprite_sheet: handles.tile_sprite_handle.clone(),
+                    sprite_number: 0,
+                };
+                sprites.insert(entity, sprite_render).expect("unable to create particle");
+                transparents.insert(entity, Transparent).expect("unable to create particle");
+                cleanup_tags.insert(entity, CleanupTag {}).expect("unable to create particle");
+            }
+
+            particles.insert(entity, particle).expect("unable to create particle");
+            transforms.insert(entity, transform).expect("unable to create particle");
+            tints.insert(entity, Tint(color)).expect("unable to create particle");
+        }
+    }
+}
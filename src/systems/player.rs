@@ -1,16 +1,27 @@
 use amethyst::{
-    core::{timing::Time, Transform},
+    core::{timing::Time, Hidden, Transform},
     derive::SystemDesc,
-    ecs::{Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, SystemData, WriteStorage},
+    ecs::{Entities, Join, Read, ReadExpect, ReadStorage, System, SystemData, Write, WriteStorage},
     input::{InputHandler, StringBindings},
+    renderer::Camera,
+    window::ScreenDimensions,
 };
 
+use crate::components::{animation::Animation, cleanup::CleanupTag, cooldown::Cooldown};
+
 use crate::entities::{
     laser::{spawn_laser, Laser},
-    player::Player,
+    player::{Player, PlayerIndex},
 };
 
-use crate::resources::playablearea::PlayableArea;
+use crate::resources::{
+    animations::ShotAnimations,
+    audio::{SoundEffect, SoundQueue},
+    controls::{player_binding, screen_to_world, AimMode, ControlsConfig},
+    handles::GameplayHandles,
+    playablearea::PlayableArea,
+    pool::{Pool, ShotStorages},
+};
 
 use amethyst_rendy::sprite::SpriteRender;
 
@@ -25,56 +36,140 @@ impl<'s> System<'s> for PlayerSystem {
     type SystemData = (
         WriteStorage<'s, Transform>,
         WriteStorage<'s, Player>,
+        ReadStorage<'s, PlayerIndex>,
+        WriteStorage<'s, Cooldown>,
         Read<'s, InputHandler<StringBindings>>,
         Entities<'s>,
-        ReadStorage<'s, SpriteRender>,
-        ReadExpect<'s, LazyUpdate>,
+        WriteStorage<'s, SpriteRender>,
+        WriteStorage<'s, Laser>,
+        WriteStorage<'s, Hidden>,
+        WriteStorage<'s, CleanupTag>,
+        Write<'s, Pool<Laser>>,
+        ReadExpect<'s, GameplayHandles>,
         Read<'s, Time>,
         Read<'s, PlayableArea>,
+        Read<'s, ControlsConfig>,
+        ReadStorage<'s, Camera>,
+        ReadExpect<'s, ScreenDimensions>,
+        Write<'s, SoundQueue>,
+        WriteStorage<'s, Animation>,
+        Read<'s, ShotAnimations>,
     );
 
     fn run(
         &mut self,
-        (mut transforms, mut characters, input, entities, sprites, lazy_update, time, playable_area): Self::SystemData,
+        (
+            mut transforms,
+            mut characters,
+            player_indexes,
+            mut cooldowns,
+            input,
+            entities,
+            mut sprites,
+            mut lasers,
+            mut hiddens,
+            mut cleanup_tags,
+            mut laser_pool,
+            handles,
+            time,
+            playable_area,
+            controls,
+            cameras,
+            dimensions,
+            mut sounds,
+            mut animations,
+            shot_animations,
+        ): Self::SystemData,
     ) {
-        for (character, transform, sprite) in (&mut characters, &mut transforms, &sprites).join() {
+        // in mouse aim mode lasers are aimed at the cursor, so find it in world
+        // coordinates before the transforms are borrowed mutably below
+        let cursor = input.mouse_position().and_then(|(mouse_x, mouse_y)| {
+            (&cameras, &transforms).join().next().map(|(camera, camera_transform)| {
+                screen_to_world(mouse_x, mouse_y, &dimensions, camera, camera_transform)
+            })
+        });
+
+        // lasers are fired once the player transforms are no longer borrowed
+        let mut fired = Vec::new();
+
+        for (character, player_index, cooldown, transform) in (
+            &mut characters,
+            (&player_indexes).maybe(),
+            &mut cooldowns,
+            &mut transforms,
+        )
+            .join()
+        {
+            let index = player_index.map_or(0, |i| i.0);
+            let axis = |name: &str| input.axis_value(&player_binding(name, index)).unwrap_or(0.0);
+
             // the input names here are defined in config/bindings.ron.
             // in general 0 is no movement, 1 is positive, and -1 is negative
             // (analog sticks might have other degrees of > 0 and < 0)
-            let movement_x = input.axis_value("x_axis");
-            let movement_y = input.axis_value("y_axis");
+            let (x_amt, y_amt) = controls.apply_deadzone(axis("x_axis"), axis("y_axis"));
+            character.moving = x_amt != 0.0 || y_amt != 0.0;
 
             // update the x and y coordinates based on current input (if there is
-            // no movement then new_x and new_y will equal 0 and the transform
+            // no movement then x_amt and y_amt will equal 0 and the transform
             // coordinates will not be changed)
-            if let Some(x_amt) = movement_x {
-                let new_x = time.delta_seconds() * x_amt * character.get_speed() + transform.translation().x;
-                transform.set_translation_x(playable_area.clamp_x(new_x));
-            }
+            let new_x = time.delta_seconds() * x_amt * character.get_speed() + transform.translation().x;
+            transform.set_translation_x(playable_area.clamp_x(new_x));
 
-            if let Some(y_amt) = movement_y {
-                let new_y = time.delta_seconds() * y_amt * character.get_speed() + transform.translation().y;
-                transform.set_translation_y(playable_area.clamp_y(new_y));
-            }
+            let new_y = time.delta_seconds() * y_amt * character.get_speed() + transform.translation().y;
+            transform.set_translation_y(playable_area.clamp_y(new_y));
 
             // this tracks whether or not the player is shooting. it makes sense to stay
             // here for now, mostly to avoid weird issues in the future that might allow
             // firing lasers without a player entity
-            let laser_x = input.axis_value("x_laser");
-            let laser_y = input.axis_value("y_laser");
+            let aim_mode = if index == 0 { controls.aim_mode } else { AimMode::Axes };
+            let (laser_x, laser_y) = match aim_mode {
+                AimMode::Axes => controls.apply_deadzone(axis("x_laser"), axis("y_laser")),
+                // aim from the player towards the cursor, but only while firing
+                AimMode::Mouse => match cursor {
+                    Some((cursor_x, cursor_y)) if input.action_is_down("fire").unwrap_or(false) => (
+                        cursor_x - transform.translation().x,
+                        cursor_y - transform.translation().y,
+                    ),
+                    _ => (0.0, 0.0),
+                },
+            };
 
             // this computes Some(laser_with_direction) or None, based on input
-            // (e.g. right and up arrows will create Some(Laser::new(RightUp)))
-            let maybe_laser = Laser::from_coordinates(laser_x, laser_y, character.laser_speed);
+            // (e.g. right and up arrows will create a laser aimed up and to the right,
+            // and an analog stick can aim at any angle)
+            let maybe_laser = Laser::from_coordinates(Some(laser_x), Some(laser_y), character.laser_speed, index);
 
-            // cloning the sprite sheet here is pretty hacky...
-            // it should be a prefab or shared resource of some kind, not tied
-            // to the sprite sheet the player is using
             if let Some(laser) = maybe_laser {
-                if character.can_fire(time.delta_seconds()) {
-                    spawn_laser(sprite.clone().sprite_sheet, laser, &transform, &entities, &lazy_update);
+                if cooldown.try_fire() {
+                    fired.push((laser, transform.clone()));
                 }
             }
         }
+
+        let mut shots = ShotStorages {
+            entities: &entities,
+            transforms: &mut transforms,
+            sprites: &mut sprites,
+            hiddens: &mut hiddens,
+            cleanup_tags: &mut cleanup_tags,
+            animations: &mut animations,
+        };
+
+        for (laser, transform) in fired {
+            let spawned = spawn_laser(
+                &handles.player_sprites_handle,
+                shot_animations.laser.as_ref(),
+                laser,
+                &transform,
+                &mut lasers,
+                &mut laser_pool,
+                &mut shots,
+            );
+
+            // shots skipped at the pool cap stay quiet
+            if spawned {
+                sounds.play(SoundEffect::LaserFire);
+            }
+        }
     }
 }
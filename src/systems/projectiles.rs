@@ -1,10 +1,26 @@
 use amethyst::{
-    core::{timing::Time, Transform},
+    core::{Hidden, Transform},
     derive::SystemDesc,
-    ecs::{Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, SystemData, WriteStorage},
+    ecs::{Entities, Join, Read, ReadExpect, ReadStorage, System, SystemData, Write, WriteStorage},
 };
 
-use crate::components::launcher::{launch_projectile, Launcher};
+use crate::{
+    components::{
+        animation::Animation,
+        cleanup::CleanupTag,
+        collider::Collider,
+        cooldown::Cooldown,
+        launcher::{build_projectile, launch_projectile, recycle_projectile, Launcher, Projectile},
+        movement::Movement,
+        targeting::{player_positions, Targeting},
+    },
+    entities::player::{Player, PlayerIndex},
+    resources::{
+        animations::ShotAnimations,
+        handles::GameplayHandles,
+        pool::{Pool, ShotStorages},
+    },
+};
 
 use amethyst_rendy::sprite::SpriteRender;
 
@@ -19,23 +35,111 @@ impl<'s> System<'s> for ProjectilesSystem {
     type SystemData = (
         WriteStorage<'s, Transform>,
         WriteStorage<'s, Launcher>,
+        WriteStorage<'s, Cooldown>,
         Entities<'s>,
-        ReadStorage<'s, SpriteRender>,
-        ReadExpect<'s, LazyUpdate>,
-        Read<'s, Time>,
+        WriteStorage<'s, SpriteRender>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, PlayerIndex>,
+        ReadStorage<'s, Targeting>,
+        WriteStorage<'s, Projectile>,
+        WriteStorage<'s, Movement>,
+        WriteStorage<'s, Collider>,
+        WriteStorage<'s, Hidden>,
+        WriteStorage<'s, CleanupTag>,
+        Write<'s, Pool<Projectile>>,
+        ReadExpect<'s, GameplayHandles>,
+        WriteStorage<'s, Animation>,
+        Read<'s, ShotAnimations>,
     );
 
-    fn run(&mut self, (mut transforms, mut launchers, entities, sprites, lazy_update, time): Self::SystemData) {
-        for (launcher, transform, sprite) in (&mut launchers, &mut transforms, &sprites).join() {
-            if launcher.can_fire(time.delta_seconds()) {
-                launch_projectile(
-                    *launcher,
-                    sprite.clone().sprite_sheet,
-                    &transform,
-                    &entities,
-                    &lazy_update,
-                );
+    fn run(
+        &mut self,
+        (
+            mut transforms,
+            mut launchers,
+            mut cooldowns,
+            entities,
+            mut sprites,
+            players,
+            player_indexes,
+            targetings,
+            mut projectiles,
+            mut movements,
+            mut colliders,
+            mut hiddens,
+            mut cleanup_tags,
+            mut projectile_pool,
+            handles,
+            mut animations,
+            shot_animations,
+        ): Self::SystemData,
+    ) {
+        // clear projectiles whose launcher wants them gone after it's destroyed
+        let orphaned: Vec<_> = (&entities, &projectiles)
+            .join()
+            .filter(|(_, projectile)| projectile.owner.map_or(false, |owner| !entities.is_alive(owner)))
+            .map(|(projectile_entity, _)| projectile_entity)
+            .collect();
+
+        for projectile_entity in orphaned {
+            recycle_projectile(
+                projectile_entity,
+                &mut projectiles,
+                &mut movements,
+                &mut hiddens,
+                &mut projectile_pool,
+            );
+        }
+
+        let player_positions = player_positions(&players, &player_indexes, &transforms);
+
+        // projectiles are launched once the launcher transforms are no longer borrowed
+        let mut volleys = Vec::new();
+
+        for (launcher_entity, launcher, cooldown, transform, targeting) in (
+            &entities,
+            &mut launchers,
+            &mut cooldowns,
+            &transforms,
+            (&targetings).maybe(),
+        )
+            .join()
+        {
+            if launcher.enabled && cooldown.try_fire() {
+                let (x, y) = (transform.translation().x, transform.translation().y);
+                let target = targeting.copied().unwrap_or_default().pick_target(x, y, &player_positions);
+
+                let aim_angle = target.map(|target| {
+                    let dir = target - transform.translation();
+                    dir.y.atan2(dir.x)
+                });
+
+                for angle in launcher.next_volley(aim_angle) {
+                    volleys.push(build_projectile(launcher, launcher_entity, angle, &transform));
+                }
             }
         }
+
+        let mut shots = ShotStorages {
+            entities: &entities,
+            transforms: &mut transforms,
+            sprites: &mut sprites,
+            hiddens: &mut hiddens,
+            cleanup_tags: &mut cleanup_tags,
+            animations: &mut animations,
+        };
+
+        for components in volleys {
+            launch_projectile(
+                components,
+                &handles.enemy_sprites_handle,
+                shot_animations.projectile.as_ref(),
+                &mut projectiles,
+                &mut movements,
+                &mut colliders,
+                &mut projectile_pool,
+                &mut shots,
+            );
+        }
     }
 }
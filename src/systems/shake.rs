@@ -0,0 +1,39 @@
+use amethyst::{
+    core::{timing::Time, Transform},
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadExpect, ReadStorage, System, SystemData, Write, WriteStorage},
+    renderer::Camera,
+    window::ScreenDimensions,
+};
+
+use crate::resources::{settings::Settings, shake::ScreenShake};
+
+// moves the camera around its resting place (the middle of the screen, see
+// `init_camera`) while there's trauma left to shake off
+#[derive(SystemDesc)]
+pub struct ScreenShakeSystem;
+
+impl<'s> System<'s> for ScreenShakeSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadStorage<'s, Camera>,
+        WriteStorage<'s, Transform>,
+        Write<'s, ScreenShake>,
+        ReadExpect<'s, Settings>,
+        ReadExpect<'s, ScreenDimensions>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, (cameras, mut transforms, mut shake, settings, dimensions, time): Self::SystemData) {
+        if !settings.screen_shake {
+            shake.clear();
+        }
+
+        let (offset_x, offset_y) = shake.update(time.delta_seconds());
+
+        for (_camera, transform) in (&cameras, &mut transforms).join() {
+            transform.set_translation_x(dimensions.width() * 0.5 + offset_x);
+            transform.set_translation_y(dimensions.height() * 0.5 + offset_y);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use amethyst::{
+    core::Transform,
+    derive::SystemDesc,
+    ecs::{Entities, Entity, Join, ReadStorage, System, SystemData, WriteStorage},
+};
+
+use crate::{
+    components::{movement::Movement, steering::Steering},
+    resources::spatialgrid::SpatialGrid,
+};
+
+// a snapshot of each steering mover, so neighbors can be looked up while the
+// movement storage is being written
+struct Boid {
+    entity: Entity,
+    x: f32,
+    y: f32,
+    velocity_x: f32,
+    velocity_y: f32,
+    speed: f32,
+    steering: Steering,
+}
+
+// adds boids-style steering on top of each mover's chosen velocity. this
+// runs after `MovementTrackingSystem` and before `TransformUpdateSystem`.
+// neighbors come from a spatial grid that's rebuilt every frame, so rows of
+// 20+ enemies don't need to check every pair
+#[derive(SystemDesc)]
+pub struct SteeringSystem;
+
+impl<'s> System<'s> for SteeringSystem {
+    type SystemData = (
+        ReadStorage<'s, Transform>,
+        WriteStorage<'s, Movement>,
+        ReadStorage<'s, Steering>,
+        Entities<'s>,
+    );
+
+    fn run(&mut self, (transforms, mut movements, steerings, entities): Self::SystemData) {
+        let boids: Vec<Boid> = (&entities, &transforms, &movements, &steerings)
+            .join()
+            .map(|(entity, transform, movement, steering)| Boid {
+                entity,
+                x: transform.translation().x,
+                y: transform.translation().y,
+                velocity_x: movement.velocity_x,
+                velocity_y: movement.velocity_y,
+                speed: movement.get_speed(),
+                steering: *steering,
+            })
+            .collect();
+
+        let cell_size = boids.iter().map(|b| b.steering.query_radius()).fold(0.0, f32::max);
+        let mut grid = SpatialGrid::new(cell_size);
+        for (index, boid) in boids.iter().enumerate() {
+            grid.insert(boid.x, boid.y, index);
+        }
+
+        for (index, boid) in boids.iter().enumerate() {
+            let (steer_x, steer_y) = steer(index, boid, &boids, &grid);
+
+            if let Some(movement) = movements.get_mut(boid.entity) {
+                movement.steer_x = steer_x;
+                movement.steer_y = steer_y;
+            }
+        }
+    }
+}
+
+fn steer(index: usize, boid: &Boid, boids: &[Boid], grid: &SpatialGrid) -> (f32, f32) {
+    let steering = boid.steering;
+
+    let mut separation = (0.0, 0.0);
+    let mut heading = (0.0, 0.0);
+    let mut center = (0.0, 0.0);
+    let mut neighbors = 0;
+
+    for other_index in grid.candidates(boid.x, boid.y, steering.query_radius()) {
+        if other_index == index {
+            continue;
+        }
+
+        let other = &boids[other_index];
+        let dx = boid.x - other.x;
+        let dy = boid.y - other.y;
+        let distance = (dx * dx + dy * dy).sqrt();
+
+        // closer neighbors push harder. movers on the exact same spot
+        // can't tell which way is away, so they're split apart on x
+        if distance < steering.separation_radius {
+            let strength = 1.0 - distance / steering.separation_radius;
+            if distance > 0.0 {
+                separation.0 += dx / distance * strength;
+                separation.1 += dy / distance * strength;
+            } else {
+                separation.0 += if index < other_index { -strength } else { strength };
+            }
+        }
+
+        if distance < steering.neighbor_radius {
+            heading.0 += other.velocity_x;
+            heading.1 += other.velocity_y;
+            center.0 += other.x;
+            center.1 += other.y;
+            neighbors += 1;
+        }
+    }
+
+    let mut steer_x = separation.0 * steering.separation_weight * boid.speed;
+    let mut steer_y = separation.1 * steering.separation_weight * boid.speed;
+
+    if neighbors > 0 {
+        let count = neighbors as f32;
+
+        let (align_x, align_y) = normalize(heading.0 / count - boid.velocity_x, heading.1 / count - boid.velocity_y);
+        steer_x += align_x * steering.alignment_weight * boid.speed;
+        steer_y += align_y * steering.alignment_weight * boid.speed;
+
+        let (cohere_x, cohere_y) = normalize(center.0 / count - boid.x, center.1 / count - boid.y);
+        steer_x += cohere_x * steering.cohesion_weight * boid.speed;
+        steer_y += cohere_y * steering.cohesion_weight * boid.speed;
+    }
+
+    (steer_x, steer_y)
+}
+
+fn normalize(x: f32, y: f32) -> (f32, f32) {
+    let length = (x * x + y * y).sqrt();
+    if length > 0.0 {
+        (x / length, y / length)
+    } else {
+        (0.0, 0.0)
+    }
+}
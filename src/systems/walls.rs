@@ -0,0 +1,113 @@
+use amethyst::{
+    core::{Hidden, Transform},
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+};
+
+use crate::{
+    components::{
+        collider::Collider,
+        launcher::{recycle_projectile, Projectile},
+        movement::Movement,
+    },
+    entities::{
+        laser::{Laser, LASER_COLLIDER},
+        player::Player,
+    },
+    resources::{
+        particles::{BurstKind, ParticleBursts},
+        pool::Pool,
+        walls::Walls,
+    },
+};
+
+// keeps everything out of the level's walls. lasers and projectiles stop when
+// they hit a wall, while players and enemies are pushed back out, which lets
+// them slide along it. this runs after everything has moved for the frame
+#[derive(SystemDesc)]
+pub struct WallCollisionSystem;
+
+impl<'s> System<'s> for WallCollisionSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        WriteStorage<'s, Transform>,
+        ReadStorage<'s, Collider>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, Movement>,
+        WriteStorage<'s, Laser>,
+        WriteStorage<'s, Projectile>,
+        Read<'s, Walls>,
+        Write<'s, ParticleBursts>,
+        Entities<'s>,
+        WriteStorage<'s, Hidden>,
+        Write<'s, Pool<Laser>>,
+        Write<'s, Pool<Projectile>>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut transforms,
+            colliders,
+            players,
+            mut movements,
+            mut lasers,
+            mut projectiles,
+            walls,
+            mut bursts,
+            entities,
+            mut hiddens,
+            mut laser_pool,
+            mut projectile_pool,
+        ): Self::SystemData,
+    ) {
+        let mut blocked_lasers = Vec::new();
+        for (entity, _laser, transform) in (&entities, &lasers, &transforms).join() {
+            let (x, y) = (transform.translation().x, transform.translation().y);
+            if walls.blocks(&LASER_COLLIDER, x, y) {
+                blocked_lasers.push(entity);
+                bursts.request(BurstKind::LaserImpact, x, y);
+            }
+        }
+
+        for entity in blocked_lasers {
+            laser_pool.recycle(entity, &mut lasers, &mut hiddens);
+        }
+
+        let mut blocked_projectiles = Vec::new();
+        for (entity, _projectile, collider, transform) in (&entities, &projectiles, &colliders, &transforms).join() {
+            if walls.blocks(collider, transform.translation().x, transform.translation().y) {
+                blocked_projectiles.push(entity);
+            }
+        }
+
+        for entity in blocked_projectiles {
+            recycle_projectile(
+                entity,
+                &mut projectiles,
+                &mut movements,
+                &mut hiddens,
+                &mut projectile_pool,
+            );
+        }
+
+        // projectiles also have a movement, but they were handled above
+        for (collider, transform, player, movement, ()) in (
+            &colliders,
+            &mut transforms,
+            (&players).maybe(),
+            (&movements).maybe(),
+            !&projectiles,
+        )
+            .join()
+        {
+            if player.is_none() && movement.is_none() {
+                continue;
+            }
+
+            let (x, y) = walls.push_out(collider, transform.translation().x, transform.translation().y);
+            transform.set_translation_x(x);
+            transform.set_translation_y(y);
+        }
+    }
+}